@@ -0,0 +1,1110 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fmt;
+
+// Broad category of an error, to decide how to handle it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    // temporary failure (e.g. network), the command can be retried later
+    Retryable,
+    // the referenced entity (user, contact, group, item, file...) doesn't exist
+    NotFound,
+    // the user is not allowed to do this (e.g. missing group role, prohibited feature)
+    Permission,
+    Other,
+}
+
+// Error returned by the chat server, also used as the error of the
+// `ChatClient::api_*` methods when the server rejects a command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum ChatError {
+    Error {
+        error_type: ChatErrorType,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ErrorAgent {
+        agent_error: AgentError,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ErrorStore {
+        store_error: StoreError,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ErrorDatabase {
+        database_error: JsonValue,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl ChatError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Error { error_type, .. } => error_type.kind(),
+            Self::ErrorAgent { agent_error, .. } => agent_error.kind(),
+            Self::ErrorStore { store_error, .. } => store_error.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind() == ErrorKind::Retryable
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == ErrorKind::NotFound
+    }
+
+    pub fn is_permission(&self) -> bool {
+        self.kind() == ErrorKind::Permission
+    }
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error { error_type, .. } => write!(f, "chat error: {}", error_type),
+            Self::ErrorAgent { agent_error, .. } => write!(f, "agent error: {}", agent_error),
+            Self::ErrorStore { store_error, .. } => write!(f, "store error: {}", store_error),
+            Self::ErrorDatabase { database_error, .. } => {
+                write!(f, "database error: {}", database_error)
+            }
+            Self::Unknown(error) => write!(f, "unknown error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum ChatErrorType {
+    NoActiveUser,
+    NoConnectionUser {
+        agent_conn_id: String,
+    },
+    NoSndFileUser {
+        agent_snd_file_id: String,
+    },
+    NoRcvFileUser {
+        agent_rcv_file_id: String,
+    },
+    UserUnknown,
+    ActiveUserExists,
+    UserExists {
+        contact_name: String,
+    },
+    InvalidDisplayName {
+        display_name: String,
+        valid_name: String,
+    },
+    DifferentActiveUser {
+        command_user_id: u64,
+        active_user_id: u64,
+    },
+    CantDeleteActiveUser {
+        user_id: u64,
+    },
+    CantDeleteLastUser {
+        user_id: u64,
+    },
+    CantHideLastUser {
+        user_id: u64,
+    },
+    HiddenUserAlwaysMuted {
+        user_id: u64,
+    },
+    EmptyUserPassword {
+        user_id: u64,
+    },
+    UserAlreadyHidden {
+        user_id: u64,
+    },
+    UserNotHidden {
+        user_id: u64,
+    },
+    InvalidChatMessage {
+        message: String,
+    },
+    ContactNotFound {
+        contact_name: String,
+    },
+    ContactNotReady {
+        contact: JsonValue,
+    },
+    ContactNotActive {
+        contact: JsonValue,
+    },
+    ContactDisabled {
+        contact: JsonValue,
+    },
+    ConnectionDisabled {
+        connection: JsonValue,
+    },
+    GroupUserRole {
+        group_info: JsonValue,
+        required_role: String,
+    },
+    GroupMemberInitialRole {
+        group_info: JsonValue,
+        initial_role: String,
+    },
+    ContactIncognitoCantInvite,
+    GroupIncognitoCantInvite,
+    GroupContactRole {
+        contact_name: String,
+    },
+    GroupDuplicateMember {
+        contact_name: String,
+    },
+    GroupDuplicateMemberId,
+    GroupNotJoined {
+        group_info: JsonValue,
+    },
+    GroupMemberNotActive,
+    CantBlockMemberForSelf {
+        group_info: JsonValue,
+        member: JsonValue,
+        set_show_messages: bool,
+    },
+    GroupMemberUserRemoved,
+    GroupMemberNotFound,
+    GroupCantResendInvitation {
+        group_info: JsonValue,
+        contact_name: String,
+    },
+    GroupInternal {
+        message: String,
+    },
+    FileNotFound {
+        message: String,
+    },
+    FileSize {
+        file_path: String,
+    },
+    FileAlreadyReceiving {
+        message: String,
+    },
+    FileCancelled {
+        message: String,
+    },
+    FileCancel {
+        file_id: u64,
+        message: String,
+    },
+    FileAlreadyExists {
+        file_path: String,
+    },
+    FileRead {
+        file_path: String,
+        message: String,
+    },
+    FileWrite {
+        file_path: String,
+        message: String,
+    },
+    FileSend {
+        file_id: u64,
+        agent_error: AgentError,
+    },
+    FileRcvChunk {
+        message: String,
+    },
+    FileInternal {
+        message: String,
+    },
+    FileImageType {
+        file_path: String,
+    },
+    FileImageSize {
+        file_path: String,
+    },
+    FileNotReceived {
+        file_id: u64,
+    },
+    FileNotApproved {
+        file_id: u64,
+        unknown_servers: Vec<String>,
+    },
+    FallbackToSMPProhibited {
+        file_id: u64,
+    },
+    InlineFileProhibited {
+        file_id: u64,
+    },
+    InvalidForward,
+    InvalidChatItemUpdate,
+    InvalidChatItemDelete,
+    HasCurrentCall,
+    NoCurrentCall,
+    CallContact {
+        contact_id: u64,
+    },
+    DirectMessagesProhibited {
+        direction: String,
+        contact: JsonValue,
+    },
+    AgentVersion,
+    AgentNoSubResult {
+        agent_conn_id: String,
+    },
+    CommandError {
+        message: String,
+    },
+    AgentCommandError {
+        message: String,
+    },
+    InvalidFileDescription {
+        message: String,
+    },
+    ConnectionIncognitoChangeProhibited,
+    ConnectionUserChangeProhibited,
+    PeerChatVRangeIncompatible,
+    InternalError {
+        message: String,
+    },
+    Exception {
+        message: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl ChatErrorType {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NoActiveUser
+            | Self::UserUnknown
+            | Self::ContactNotFound { .. }
+            | Self::GroupMemberNotFound
+            | Self::FileNotFound { .. }
+            | Self::NoCurrentCall => ErrorKind::NotFound,
+            Self::GroupUserRole { .. }
+            | Self::GroupMemberInitialRole { .. }
+            | Self::GroupNotJoined { .. }
+            | Self::GroupMemberNotActive
+            | Self::GroupMemberUserRemoved
+            | Self::ContactIncognitoCantInvite
+            | Self::GroupIncognitoCantInvite
+            | Self::ContactNotActive { .. }
+            | Self::ContactDisabled { .. }
+            | Self::ConnectionDisabled { .. }
+            | Self::DirectMessagesProhibited { .. }
+            | Self::FileNotApproved { .. }
+            | Self::FallbackToSMPProhibited { .. }
+            | Self::InlineFileProhibited { .. }
+            | Self::ConnectionIncognitoChangeProhibited
+            | Self::ConnectionUserChangeProhibited => ErrorKind::Permission,
+            // the contact connection is not established yet
+            Self::ContactNotReady { .. } => ErrorKind::Retryable,
+            Self::FileSend { agent_error, .. } => agent_error.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for ChatErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoActiveUser => write!(f, "no active user"),
+            Self::NoConnectionUser { agent_conn_id } => {
+                write!(f, "no user for connection {}", agent_conn_id)
+            }
+            Self::NoSndFileUser { agent_snd_file_id } => {
+                write!(f, "no user for sent file {}", agent_snd_file_id)
+            }
+            Self::NoRcvFileUser { agent_rcv_file_id } => {
+                write!(f, "no user for received file {}", agent_rcv_file_id)
+            }
+            Self::UserUnknown => write!(f, "unknown user"),
+            Self::ActiveUserExists => write!(f, "an active user already exists"),
+            Self::UserExists { contact_name } => write!(f, "user {} already exists", contact_name),
+            Self::InvalidDisplayName {
+                display_name,
+                valid_name,
+            } => write!(
+                f,
+                "invalid display name {} (try {})",
+                display_name, valid_name
+            ),
+            Self::DifferentActiveUser {
+                command_user_id,
+                active_user_id,
+            } => write!(
+                f,
+                "command is for user {}, but the active user is {}",
+                command_user_id, active_user_id
+            ),
+            Self::CantDeleteActiveUser { .. } => write!(f, "can't delete the active user"),
+            Self::CantDeleteLastUser { .. } => write!(f, "can't delete the last user"),
+            Self::CantHideLastUser { .. } => write!(f, "can't hide the last user"),
+            Self::HiddenUserAlwaysMuted { .. } => write!(f, "hidden users are always muted"),
+            Self::EmptyUserPassword { .. } => write!(f, "user password can't be empty"),
+            Self::UserAlreadyHidden { .. } => write!(f, "user is already hidden"),
+            Self::UserNotHidden { .. } => write!(f, "user is not hidden"),
+            Self::InvalidChatMessage { message } => write!(f, "invalid chat message: {}", message),
+            Self::ContactNotFound { contact_name } => {
+                write!(f, "contact {} not found", contact_name)
+            }
+            Self::ContactNotReady { .. } => write!(f, "contact connection is not ready yet"),
+            Self::ContactNotActive { .. } => write!(f, "contact is not active"),
+            Self::ContactDisabled { .. } => write!(f, "contact is disabled"),
+            Self::ConnectionDisabled { .. } => write!(f, "connection is disabled"),
+            Self::GroupUserRole { required_role, .. } => {
+                write!(f, "this action requires the {} role", required_role)
+            }
+            Self::GroupMemberInitialRole { initial_role, .. } => {
+                write!(f, "invalid initial member role {}", initial_role)
+            }
+            Self::ContactIncognitoCantInvite => {
+                write!(f, "contacts connected incognito can't be invited")
+            }
+            Self::GroupIncognitoCantInvite => {
+                write!(f, "can't invite to a group joined incognito")
+            }
+            Self::GroupContactRole { contact_name } => {
+                write!(f, "contact {} has an invalid role", contact_name)
+            }
+            Self::GroupDuplicateMember { contact_name } => {
+                write!(f, "contact {} is already a member", contact_name)
+            }
+            Self::GroupDuplicateMemberId => write!(f, "duplicate group member ID"),
+            Self::GroupNotJoined { .. } => write!(f, "group was not joined"),
+            Self::GroupMemberNotActive => write!(f, "group member is not active"),
+            Self::CantBlockMemberForSelf { .. } => write!(f, "can't block member for self"),
+            Self::GroupMemberUserRemoved => write!(f, "user was removed from the group"),
+            Self::GroupMemberNotFound => write!(f, "group member not found"),
+            Self::GroupCantResendInvitation { contact_name, .. } => {
+                write!(f, "can't resend the invitation to {}", contact_name)
+            }
+            Self::GroupInternal { message } => write!(f, "group error: {}", message),
+            Self::FileNotFound { message } => write!(f, "file not found: {}", message),
+            Self::FileSize { file_path } => write!(f, "file {} is too large", file_path),
+            Self::FileAlreadyReceiving { message } => {
+                write!(f, "file is already being received: {}", message)
+            }
+            Self::FileCancelled { message } => write!(f, "file was cancelled: {}", message),
+            Self::FileCancel { file_id, message } => {
+                write!(f, "can't cancel file {}: {}", file_id, message)
+            }
+            Self::FileAlreadyExists { file_path } => {
+                write!(f, "file {} already exists", file_path)
+            }
+            Self::FileRead { file_path, message } => {
+                write!(f, "can't read file {}: {}", file_path, message)
+            }
+            Self::FileWrite { file_path, message } => {
+                write!(f, "can't write file {}: {}", file_path, message)
+            }
+            Self::FileSend {
+                file_id,
+                agent_error,
+            } => write!(f, "can't send file {}: {}", file_id, agent_error),
+            Self::FileRcvChunk { message } => write!(f, "can't receive file chunk: {}", message),
+            Self::FileInternal { message } => write!(f, "file error: {}", message),
+            Self::FileImageType { file_path } => {
+                write!(f, "file {} is not a supported image", file_path)
+            }
+            Self::FileImageSize { file_path } => {
+                write!(f, "image {} is too large", file_path)
+            }
+            Self::FileNotReceived { file_id } => write!(f, "file {} was not received", file_id),
+            Self::FileNotApproved {
+                file_id,
+                unknown_servers,
+            } => write!(
+                f,
+                "file {} requires approving the relays: {}",
+                file_id,
+                unknown_servers.join(", ")
+            ),
+            Self::FallbackToSMPProhibited { file_id } => {
+                write!(f, "file {} can't be sent via SMP", file_id)
+            }
+            Self::InlineFileProhibited { file_id } => {
+                write!(f, "file {} can't be sent inline", file_id)
+            }
+            Self::InvalidForward => write!(f, "items can't be forwarded"),
+            Self::InvalidChatItemUpdate => write!(f, "item can't be updated"),
+            Self::InvalidChatItemDelete => write!(f, "item can't be deleted"),
+            Self::HasCurrentCall => write!(f, "there is already a call in progress"),
+            Self::NoCurrentCall => write!(f, "there is no call in progress"),
+            Self::CallContact { contact_id } => {
+                write!(f, "call is not with contact {}", contact_id)
+            }
+            Self::DirectMessagesProhibited { .. } => {
+                write!(f, "direct messages are prohibited")
+            }
+            Self::AgentVersion => write!(f, "unsupported agent version"),
+            Self::AgentNoSubResult { agent_conn_id } => {
+                write!(f, "no subscription result for connection {}", agent_conn_id)
+            }
+            Self::CommandError { message } => write!(f, "invalid command: {}", message),
+            Self::AgentCommandError { message } => write!(f, "agent command error: {}", message),
+            Self::InvalidFileDescription { message } => {
+                write!(f, "invalid file description: {}", message)
+            }
+            Self::ConnectionIncognitoChangeProhibited => {
+                write!(f, "can't change incognito mode of the connection")
+            }
+            Self::ConnectionUserChangeProhibited => {
+                write!(f, "can't change the user of the connection")
+            }
+            Self::PeerChatVRangeIncompatible => {
+                write!(f, "the chat version of the peer is incompatible")
+            }
+            Self::InternalError { message } => write!(f, "internal error: {}", message),
+            Self::Exception { message } => write!(f, "exception: {}", message),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum StoreError {
+    DuplicateName,
+    UserNotFound {
+        user_id: u64,
+    },
+    UserNotFoundByName {
+        contact_name: String,
+    },
+    UserNotFoundByContactId {
+        contact_id: u64,
+    },
+    UserNotFoundByGroupId {
+        group_id: u64,
+    },
+    UserNotFoundByFileId {
+        file_id: u64,
+    },
+    UserNotFoundByContactRequestId {
+        contact_request_id: u64,
+    },
+    ContactNotFound {
+        contact_id: u64,
+    },
+    ContactNotFoundByName {
+        contact_name: String,
+    },
+    ContactNotFoundByMemberId {
+        group_member_id: u64,
+    },
+    ContactNotReady {
+        contact_name: String,
+    },
+    DuplicateContactLink,
+    UserContactLinkNotFound,
+    ContactRequestNotFound {
+        contact_request_id: u64,
+    },
+    ContactRequestNotFoundByName {
+        contact_name: String,
+    },
+    GroupNotFound {
+        group_id: u64,
+    },
+    GroupNotFoundByName {
+        group_name: String,
+    },
+    GroupMemberNameNotFound {
+        group_id: u64,
+        group_member_name: String,
+    },
+    GroupMemberNotFound {
+        group_member_id: u64,
+    },
+    GroupMemberNotFoundByMemberId {
+        member_id: String,
+    },
+    MemberContactGroupMemberNotFound {
+        contact_id: u64,
+    },
+    GroupWithoutUser,
+    DuplicateGroupMember,
+    GroupAlreadyJoined,
+    GroupInvitationNotFound,
+    SndFileNotFound {
+        file_id: u64,
+    },
+    SndFileInvalid {
+        file_id: u64,
+    },
+    RcvFileNotFound {
+        file_id: u64,
+    },
+    RcvFileDescrNotFound {
+        file_id: u64,
+    },
+    FileNotFound {
+        file_id: u64,
+    },
+    RcvFileInvalid {
+        file_id: u64,
+    },
+    RcvFileInvalidDescrPart,
+    SndFileNotFoundXFTP {
+        agent_snd_file_id: String,
+    },
+    RcvFileNotFoundXFTP {
+        agent_rcv_file_id: String,
+    },
+    ConnectionNotFound {
+        agent_conn_id: String,
+    },
+    ConnectionNotFoundById {
+        conn_id: u64,
+    },
+    ConnectionNotFoundByMemberId {
+        group_member_id: u64,
+    },
+    PendingConnectionNotFound {
+        conn_id: u64,
+    },
+    IntroNotFound,
+    #[serde(rename = "uniqueID")]
+    UniqueId,
+    LargeMsg,
+    InternalError {
+        message: String,
+    },
+    #[serde(rename = "dBException")]
+    DbException {
+        message: String,
+    },
+    BadChatItem {
+        item_id: u64,
+    },
+    ChatItemNotFound {
+        item_id: u64,
+    },
+    ChatItemNotFoundByText {
+        text: String,
+    },
+    ChatItemSharedMsgIdNotFound {
+        shared_msg_id: String,
+    },
+    ChatItemNotFoundByFileId {
+        file_id: u64,
+    },
+    ChatItemNotFoundByContactId {
+        contact_id: u64,
+    },
+    ChatItemNotFoundByGroupId {
+        group_id: u64,
+    },
+    ProfileNotFound {
+        profile_id: u64,
+    },
+    DuplicateGroupLink {
+        group_info: JsonValue,
+    },
+    GroupLinkNotFound {
+        group_info: JsonValue,
+    },
+    HostMemberIdNotFound {
+        group_id: u64,
+    },
+    ContactNotFoundByFileId {
+        file_id: u64,
+    },
+    NoGroupSndStatus {
+        item_id: u64,
+        group_member_id: u64,
+    },
+    DuplicateGroupMessage {
+        group_id: u64,
+        shared_msg_id: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl StoreError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::UserNotFound { .. }
+            | Self::UserNotFoundByName { .. }
+            | Self::UserNotFoundByContactId { .. }
+            | Self::UserNotFoundByGroupId { .. }
+            | Self::UserNotFoundByFileId { .. }
+            | Self::UserNotFoundByContactRequestId { .. }
+            | Self::ContactNotFound { .. }
+            | Self::ContactNotFoundByName { .. }
+            | Self::ContactNotFoundByMemberId { .. }
+            | Self::UserContactLinkNotFound
+            | Self::ContactRequestNotFound { .. }
+            | Self::ContactRequestNotFoundByName { .. }
+            | Self::GroupNotFound { .. }
+            | Self::GroupNotFoundByName { .. }
+            | Self::GroupMemberNameNotFound { .. }
+            | Self::GroupMemberNotFound { .. }
+            | Self::GroupMemberNotFoundByMemberId { .. }
+            | Self::MemberContactGroupMemberNotFound { .. }
+            | Self::GroupInvitationNotFound
+            | Self::SndFileNotFound { .. }
+            | Self::RcvFileNotFound { .. }
+            | Self::RcvFileDescrNotFound { .. }
+            | Self::FileNotFound { .. }
+            | Self::SndFileNotFoundXFTP { .. }
+            | Self::RcvFileNotFoundXFTP { .. }
+            | Self::ConnectionNotFound { .. }
+            | Self::ConnectionNotFoundById { .. }
+            | Self::ConnectionNotFoundByMemberId { .. }
+            | Self::PendingConnectionNotFound { .. }
+            | Self::IntroNotFound
+            | Self::ChatItemNotFound { .. }
+            | Self::ChatItemNotFoundByText { .. }
+            | Self::ChatItemSharedMsgIdNotFound { .. }
+            | Self::ChatItemNotFoundByFileId { .. }
+            | Self::ChatItemNotFoundByContactId { .. }
+            | Self::ChatItemNotFoundByGroupId { .. }
+            | Self::ProfileNotFound { .. }
+            | Self::GroupLinkNotFound { .. }
+            | Self::HostMemberIdNotFound { .. }
+            | Self::ContactNotFoundByFileId { .. } => ErrorKind::NotFound,
+            Self::ContactNotReady { .. } => ErrorKind::Retryable,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateName => write!(f, "name is already in use"),
+            Self::UserNotFound { user_id } => write!(f, "user {} not found", user_id),
+            Self::UserNotFoundByName { contact_name } => {
+                write!(f, "user {} not found", contact_name)
+            }
+            Self::UserNotFoundByContactId { contact_id } => {
+                write!(f, "user of contact {} not found", contact_id)
+            }
+            Self::UserNotFoundByGroupId { group_id } => {
+                write!(f, "user of group {} not found", group_id)
+            }
+            Self::UserNotFoundByFileId { file_id } => {
+                write!(f, "user of file {} not found", file_id)
+            }
+            Self::UserNotFoundByContactRequestId { contact_request_id } => write!(
+                f,
+                "user of contact request {} not found",
+                contact_request_id
+            ),
+            Self::ContactNotFound { contact_id } => write!(f, "contact {} not found", contact_id),
+            Self::ContactNotFoundByName { contact_name } => {
+                write!(f, "contact {} not found", contact_name)
+            }
+            Self::ContactNotFoundByMemberId { group_member_id } => {
+                write!(f, "contact of member {} not found", group_member_id)
+            }
+            Self::ContactNotReady { contact_name } => {
+                write!(f, "contact {} is not ready yet", contact_name)
+            }
+            Self::DuplicateContactLink => write!(f, "contact address already exists"),
+            Self::UserContactLinkNotFound => write!(f, "contact address not found"),
+            Self::ContactRequestNotFound { contact_request_id } => {
+                write!(f, "contact request {} not found", contact_request_id)
+            }
+            Self::ContactRequestNotFoundByName { contact_name } => {
+                write!(f, "contact request from {} not found", contact_name)
+            }
+            Self::GroupNotFound { group_id } => write!(f, "group {} not found", group_id),
+            Self::GroupNotFoundByName { group_name } => {
+                write!(f, "group {} not found", group_name)
+            }
+            Self::GroupMemberNameNotFound {
+                group_id,
+                group_member_name,
+            } => write!(
+                f,
+                "member {} not found in group {}",
+                group_member_name, group_id
+            ),
+            Self::GroupMemberNotFound { group_member_id } => {
+                write!(f, "group member {} not found", group_member_id)
+            }
+            Self::GroupMemberNotFoundByMemberId { member_id } => {
+                write!(f, "group member {} not found", member_id)
+            }
+            Self::MemberContactGroupMemberNotFound { contact_id } => {
+                write!(f, "group member of contact {} not found", contact_id)
+            }
+            Self::GroupWithoutUser => write!(f, "group has no user membership"),
+            Self::DuplicateGroupMember => write!(f, "duplicate group member"),
+            Self::GroupAlreadyJoined => write!(f, "group is already joined"),
+            Self::GroupInvitationNotFound => write!(f, "group invitation not found"),
+            Self::SndFileNotFound { file_id } => write!(f, "sent file {} not found", file_id),
+            Self::SndFileInvalid { file_id } => write!(f, "sent file {} is invalid", file_id),
+            Self::RcvFileNotFound { file_id } => write!(f, "received file {} not found", file_id),
+            Self::RcvFileDescrNotFound { file_id } => {
+                write!(f, "description of file {} not found", file_id)
+            }
+            Self::FileNotFound { file_id } => write!(f, "file {} not found", file_id),
+            Self::RcvFileInvalid { file_id } => write!(f, "received file {} is invalid", file_id),
+            Self::RcvFileInvalidDescrPart => write!(f, "invalid file description part"),
+            Self::SndFileNotFoundXFTP { agent_snd_file_id } => {
+                write!(f, "sent XFTP file {} not found", agent_snd_file_id)
+            }
+            Self::RcvFileNotFoundXFTP { agent_rcv_file_id } => {
+                write!(f, "received XFTP file {} not found", agent_rcv_file_id)
+            }
+            Self::ConnectionNotFound { agent_conn_id } => {
+                write!(f, "connection {} not found", agent_conn_id)
+            }
+            Self::ConnectionNotFoundById { conn_id } => {
+                write!(f, "connection {} not found", conn_id)
+            }
+            Self::ConnectionNotFoundByMemberId { group_member_id } => {
+                write!(f, "connection of member {} not found", group_member_id)
+            }
+            Self::PendingConnectionNotFound { conn_id } => {
+                write!(f, "pending connection {} not found", conn_id)
+            }
+            Self::IntroNotFound => write!(f, "member introduction not found"),
+            Self::UniqueId => write!(f, "could not generate a unique ID"),
+            Self::LargeMsg => write!(f, "message is too large"),
+            Self::InternalError { message } => write!(f, "internal error: {}", message),
+            Self::DbException { message } => write!(f, "database exception: {}", message),
+            Self::BadChatItem { item_id } => write!(f, "chat item {} is invalid", item_id),
+            Self::ChatItemNotFound { item_id } => write!(f, "chat item {} not found", item_id),
+            Self::ChatItemNotFoundByText { text } => {
+                write!(f, "chat item with text {:?} not found", text)
+            }
+            Self::ChatItemSharedMsgIdNotFound { shared_msg_id } => {
+                write!(f, "chat item with message ID {} not found", shared_msg_id)
+            }
+            Self::ChatItemNotFoundByFileId { file_id } => {
+                write!(f, "chat item of file {} not found", file_id)
+            }
+            Self::ChatItemNotFoundByContactId { contact_id } => {
+                write!(f, "chat item of contact {} not found", contact_id)
+            }
+            Self::ChatItemNotFoundByGroupId { group_id } => {
+                write!(f, "chat item of group {} not found", group_id)
+            }
+            Self::ProfileNotFound { profile_id } => write!(f, "profile {} not found", profile_id),
+            Self::DuplicateGroupLink { .. } => write!(f, "group link already exists"),
+            Self::GroupLinkNotFound { .. } => write!(f, "group link not found"),
+            Self::HostMemberIdNotFound { group_id } => {
+                write!(f, "host member of group {} not found", group_id)
+            }
+            Self::ContactNotFoundByFileId { file_id } => {
+                write!(f, "contact of file {} not found", file_id)
+            }
+            Self::NoGroupSndStatus {
+                item_id,
+                group_member_id,
+            } => write!(
+                f,
+                "no delivery status of item {} for member {}",
+                item_id, group_member_id
+            ),
+            Self::DuplicateGroupMessage {
+                group_id,
+                shared_msg_id,
+            } => write!(
+                f,
+                "duplicate message {} in group {}",
+                shared_msg_id, group_id
+            ),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// Errors of the SMP agent, which handles the connections to the servers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum AgentError {
+    // invalid command
+    Cmd {
+        cmd_err: JsonValue,
+        #[serde(default)]
+        err_context: String,
+    },
+    // connection errors
+    Conn {
+        conn_err: ConnectionErrorType,
+    },
+    // SMP server errors
+    Smp {
+        server_address: String,
+        smp_err: JsonValue,
+    },
+    // notifications server errors
+    Ntf {
+        server_address: String,
+        ntf_err: JsonValue,
+    },
+    // XFTP server errors
+    Xftp {
+        server_address: String,
+        xftp_err: JsonValue,
+    },
+    // XFTP file errors
+    File {
+        file_err: JsonValue,
+    },
+    // SMP proxy errors
+    Proxy {
+        proxy_server: String,
+        relay_server: String,
+        proxy_err: JsonValue,
+    },
+    // remote control errors
+    Rcp {
+        rcp_err: JsonValue,
+    },
+    // errors connecting to the servers
+    Broker {
+        broker_address: String,
+        broker_err: BrokerErrorType,
+    },
+    // errors of the agent protocol
+    Agent {
+        agent_err: JsonValue,
+    },
+    Internal {
+        internal_err: String,
+    },
+    Critical {
+        offer_restart: bool,
+        critical_err: String,
+    },
+    // the agent is not running
+    Inactive,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl AgentError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Conn {
+                conn_err: ConnectionErrorType::NotFound,
+            } => ErrorKind::NotFound,
+            Self::Conn {
+                conn_err: ConnectionErrorType::NotAvailable,
+            } => ErrorKind::Retryable,
+            Self::Broker {
+                broker_err:
+                    BrokerErrorType::Network
+                    | BrokerErrorType::Timeout
+                    | BrokerErrorType::Host
+                    | BrokerErrorType::NoService,
+                ..
+            } => ErrorKind::Retryable,
+            Self::Inactive => ErrorKind::Retryable,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cmd {
+                cmd_err,
+                err_context,
+            } => write!(f, "command error {} ({})", cmd_err, err_context),
+            Self::Conn { conn_err } => write!(f, "connection error: {}", conn_err),
+            Self::Smp {
+                server_address,
+                smp_err,
+            } => write!(f, "SMP server {} error: {}", server_address, smp_err),
+            Self::Ntf {
+                server_address,
+                ntf_err,
+            } => write!(
+                f,
+                "notification server {} error: {}",
+                server_address, ntf_err
+            ),
+            Self::Xftp {
+                server_address,
+                xftp_err,
+            } => write!(f, "XFTP server {} error: {}", server_address, xftp_err),
+            Self::File { file_err } => write!(f, "file error: {}", file_err),
+            Self::Proxy {
+                proxy_server,
+                relay_server,
+                proxy_err,
+            } => write!(
+                f,
+                "proxy {} error forwarding to {}: {}",
+                proxy_server, relay_server, proxy_err
+            ),
+            Self::Rcp { rcp_err } => write!(f, "remote control error: {}", rcp_err),
+            Self::Broker {
+                broker_address,
+                broker_err,
+            } => write!(f, "server {} error: {}", broker_address, broker_err),
+            Self::Agent { agent_err } => write!(f, "agent protocol error: {}", agent_err),
+            Self::Internal { internal_err } => write!(f, "internal error: {}", internal_err),
+            Self::Critical { critical_err, .. } => write!(f, "critical error: {}", critical_err),
+            Self::Inactive => write!(f, "agent is not active"),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(tag = "type")]
+pub enum ConnectionErrorType {
+    // the connection doesn't exist (e.g. it was deleted)
+    NotFound,
+    Duplicate,
+    Simplex,
+    NotAccepted,
+    // the other side is not connected yet
+    NotAvailable,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl fmt::Display for ConnectionErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "connection not found"),
+            Self::Duplicate => write!(f, "connection already exists"),
+            Self::Simplex => write!(f, "connection is simplex (no reply queue)"),
+            Self::NotAccepted => write!(f, "connection was not accepted"),
+            Self::NotAvailable => write!(f, "connection is not available"),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(tag = "type")]
+pub enum BrokerErrorType {
+    Response {
+        #[serde(rename = "respErr")]
+        resp_err: String,
+    },
+    Unexpected {
+        #[serde(rename = "respErr")]
+        resp_err: String,
+    },
+    Network,
+    Host,
+    NoService,
+    Transport {
+        #[serde(rename = "transportErr")]
+        transport_err: JsonValue,
+    },
+    Timeout,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl fmt::Display for BrokerErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Response { resp_err } => write!(f, "invalid response: {}", resp_err),
+            Self::Unexpected { resp_err } => write!(f, "unexpected response: {}", resp_err),
+            Self::Network => write!(f, "network error"),
+            Self::Host => write!(f, "host unavailable"),
+            Self::NoService => write!(f, "no service at the server address"),
+            Self::Transport { transport_err } => write!(f, "transport error: {}", transport_err),
+            Self::Timeout => write!(f, "timeout"),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// Reason why a chat item failed to be sent (or is being retried)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum SndError {
+    // the recipient's queue doesn't exist anymore (e.g. the contact deleted the connection)
+    Auth,
+    // the recipient's queue is full
+    Quota,
+    // the message expired before it could be sent
+    Expired,
+    Relay {
+        srv_error: SrvError,
+    },
+    Proxy {
+        proxy_server: String,
+        srv_error: SrvError,
+    },
+    // error of the destination server when sending via a proxy
+    ProxyRelay {
+        proxy_server: String,
+        srv_error: SrvError,
+    },
+    Other {
+        snd_error: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl fmt::Display for SndError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auth => write!(f, "recipient not found"),
+            Self::Quota => write!(f, "recipient queue is full"),
+            Self::Expired => write!(f, "message expired"),
+            Self::Relay { srv_error } => write!(f, "server error: {}", srv_error),
+            Self::Proxy {
+                proxy_server,
+                srv_error,
+            } => write!(f, "proxy {} error: {}", proxy_server, srv_error),
+            Self::ProxyRelay {
+                proxy_server,
+                srv_error,
+            } => write!(f, "server error via proxy {}: {}", proxy_server, srv_error),
+            Self::Other { snd_error } => write!(f, "{}", snd_error),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum SrvError {
+    // the server host is unavailable
+    Host,
+    // the server doesn't support the protocol version
+    Version,
+    Other {
+        srv_error: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl fmt::Display for SrvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Host => write!(f, "host unavailable"),
+            Self::Version => write!(f, "unsupported version"),
+            Self::Other { srv_error } => write!(f, "{}", srv_error),
+            Self::Unknown(error) => write!(f, "{}", error),
+        }
+    }
+}
@@ -1,21 +1,30 @@
+mod errors;
+mod markdown;
 mod responses;
+mod timestamp;
 mod types;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
 use futures_util::{
-    stream::{SplitSink, SplitStream},
-    SinkExt, StreamExt,
+    stream::{self, SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
 };
+use image::codecs::jpeg::JpegEncoder;
+pub use markdown::*;
 pub use responses::*;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc, Mutex,
     },
 };
-use std::{sync::mpsc, time::Duration};
+use std::{
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 use tokio::net::TcpStream;
 use tokio::task::JoinHandle;
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
@@ -25,6 +34,14 @@ type ChatWebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
 type CorrId = String;
 type MessageQueue = mpsc::Receiver<ChatSrvResponse>;
 
+// Maximum number of messages kept while waiting for a specific message,
+// after which the oldest ones are dropped
+const MAX_PENDING_MESSAGES: usize = 1000;
+
+// Client of the WebSocket API of the chat server.
+// When the server rejects a command, the `api_*` methods return its error as a
+// `ChatError`, which can be recovered with `err.downcast_ref::<ChatError>()`
+// to check its `kind()`
 #[derive(Debug)]
 pub struct ChatClient {
     uri: String,
@@ -34,6 +51,9 @@ pub struct ChatClient {
     listener_handle: JoinHandle<()>,
     command_waiters: Arc<Mutex<HashMap<CorrId, mpsc::Sender<ChatResponse>>>>,
     message_queue: MessageQueue, // Note that command_waiters has precedence over message_queue
+    // Messages taken out of the message queue while waiting for a specific
+    // message; they are returned by `next_message` before the queue itself
+    pending_messages: VecDeque<ChatSrvResponse>,
 }
 
 #[derive(Serialize, Debug)]
@@ -82,6 +102,7 @@ impl ChatClient {
             listener_handle,
             command_waiters,
             message_queue: rx,
+            pending_messages: VecDeque::new(),
             timeout: Duration::from_millis(3000),
         };
 
@@ -186,38 +207,200 @@ impl ChatClient {
     }
 
     pub async fn next_message(&mut self) -> Result<ChatSrvResponse> {
+        if let Some(message) = self.pending_messages.pop_front() {
+            return Ok(message);
+        }
+
         Ok(self.message_queue.recv()?)
     }
 
+    // Waits for a message matching `predicate`, keeping every other message
+    // so that it is still returned by `next_message`
+    async fn wait_for_message<F>(
+        &mut self,
+        timeout: Option<Duration>,
+        mut predicate: F,
+    ) -> Result<ChatResponse>
+    where
+        F: FnMut(&ChatResponse) -> bool,
+    {
+        if let Some(pos) = self
+            .pending_messages
+            .iter()
+            .position(|message| predicate(&message.resp))
+        {
+            return Ok(self.pending_messages.remove(pos).unwrap().resp);
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let message = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    self.message_queue.recv_timeout(remaining)?
+                }
+                None => self.message_queue.recv()?,
+            };
+
+            if predicate(&message.resp) {
+                return Ok(message.resp);
+            }
+
+            if self.pending_messages.len() == MAX_PENDING_MESSAGES {
+                let dropped = self.pending_messages.pop_front();
+                log::warn!("Too many pending messages, dropping {:?}", dropped);
+            }
+            self.pending_messages.push_back(message);
+        }
+    }
+
     // Simplified APIs
     pub async fn api_get_active_user(&mut self) -> Result<User> {
         let resp = self.send_command("/u").await?;
         let ChatResponse::ActiveUser { user, .. } = resp else {
-            bail!("The command response does not match the expected type");
+            return Err(unexpected_response(resp));
         };
 
         Ok(user)
     }
 
+    // Updates the profile of a user, which is sent to all of their contacts
+    pub async fn api_update_profile(&mut self, user_id: u64, profile: Profile) -> Result<Profile> {
+        let profile_json = serde_json::to_string(&profile)?;
+        let cmd = format!("/_profile {} {}", user_id, profile_json);
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::UserProfileUpdated { to_profile, .. } => Ok(to_profile),
+            ChatResponse::UserProfileNoChange { .. } => Ok(profile),
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
     pub async fn api_chats(&mut self) -> Result<Vec<Chat>> {
         let resp = self.send_command("/chats").await?;
         let ChatResponse::Chats { chats, .. } = resp else {
-            bail!("The command response does not match the expected type");
+            return Err(unexpected_response(resp));
         };
 
         Ok(chats)
     }
 
+    pub async fn api_get_chat(
+        &mut self,
+        chat_ref: ChatRef,
+        pagination: Pagination,
+        search: Option<&str>,
+    ) -> Result<Chat> {
+        let mut cmd = format!("/_get chat {} {}", chat_ref, pagination);
+        if let Some(search) = search {
+            cmd = format!("{} search={}", cmd, search);
+        }
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ApiChat { chat, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(chat)
+    }
+
+    // Walks the history of a chat from the newest items to the oldest ones,
+    // yielding pages of up to `page_size` items (each ordered from oldest to newest)
+    pub fn api_chat_history(
+        &mut self,
+        chat_ref: ChatRef,
+        page_size: u64,
+    ) -> impl Stream<Item = Result<Vec<ChatItem>>> + '_ {
+        let first_page = Some(Pagination::Last { count: page_size });
+        stream::unfold((self, first_page), move |(client, pagination)| async move {
+            let pagination = pagination?;
+            let page = client
+                .api_get_chat(chat_ref, pagination, None)
+                .await
+                .map(|chat| chat.chat_items);
+
+            // A short page means that the start of the chat was reached
+            let next_page = match page {
+                Ok(ref chat_items) if chat_items.len() as u64 == page_size => {
+                    chat_items.first().map(|chat_item| Pagination::Before {
+                        item_id: chat_item.meta.item_id,
+                        count: page_size,
+                    })
+                }
+                _ => None,
+            };
+
+            match page {
+                Ok(ref chat_items) if chat_items.is_empty() => None,
+                page => Some((page, (client, next_page))),
+            }
+        })
+    }
+
+    // Marks the items of a chat as read, either all of them or only
+    // the ones in `item_ids`
+    pub async fn api_chat_read(
+        &mut self,
+        chat_ref: ChatRef,
+        item_ids: Option<&[u64]>,
+    ) -> Result<()> {
+        let cmd = match item_ids {
+            Some([]) => bail!("No chat items to mark as read"),
+            Some(item_ids) => format!("/_read chat items {} {}", chat_ref, join_ids(item_ids)),
+            None => format!("/_read chat {}", chat_ref),
+        };
+        let resp = self.send_command(&cmd).await?;
+        let (ChatResponse::CmdOk { .. } | ChatResponse::ItemsReadForChat { .. }) = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(())
+    }
+
+    // Sets or clears the "unread" mark of a chat (see `ChatStats::unread_chat`)
+    pub async fn api_chat_unread(&mut self, chat_ref: ChatRef, unread: bool) -> Result<()> {
+        let cmd = format!(
+            "/_unread chat {} {}",
+            chat_ref,
+            if unread { "on" } else { "off" }
+        );
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::CmdOk { .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(())
+    }
+
+    pub async fn api_set_chat_settings(
+        &mut self,
+        chat_ref: ChatRef,
+        settings: ChatSettings,
+    ) -> Result<()> {
+        let settings_json = serde_json::to_string(&settings)?;
+        let cmd = format!("/_settings {} {}", chat_ref, settings_json);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::CmdOk { .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(())
+    }
+
     pub async fn api_get_user_address(&mut self) -> Result<Option<ConnLinkContact>> {
         let resp = self.send_command("/show_address").await?;
         match resp {
             ChatResponse::ContactLink { contact_link, .. } => {
                 Ok(Some(contact_link.conn_link_contact))
             }
-            ChatResponse::ChatCmdError { .. } => Ok(None),
-            _ => {
-                bail!("The command response does not match the expected type");
-            }
+            ChatResponse::ChatCmdError {
+                chat_error:
+                    ChatError::ErrorStore {
+                        store_error: StoreError::UserContactLinkNotFound,
+                        ..
+                    },
+                ..
+            } => Ok(None),
+            resp => Err(unexpected_response(resp)),
         }
     }
 
@@ -227,7 +410,7 @@ impl ChatClient {
             conn_link_contact, ..
         } = resp
         else {
-            bail!("The command response does not match the expected type");
+            return Err(unexpected_response(resp));
         };
 
         Ok(conn_link_contact)
@@ -237,12 +420,298 @@ impl ChatClient {
         let cmd = format!("/_members #{}", group_id);
         let resp = self.send_command(&cmd).await?;
         let ChatResponse::GroupMembers { group, .. } = resp else {
-            bail!("The command response does not match the expected type");
+            return Err(unexpected_response(resp));
         };
 
         Ok(group.members)
     }
 
+    pub async fn api_add_member(
+        &mut self,
+        group_id: u64,
+        contact_id: u64,
+        role: GroupMemberRole,
+    ) -> Result<GroupMember> {
+        check_role(&role)?;
+        let cmd = format!("/_add #{} {} {}", group_id, contact_id, role);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::SentGroupInvitation { member, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(member)
+    }
+
+    // Accepts a group invitation (see `ChatResponse::ReceivedGroupInvitation`)
+    pub async fn api_join_group(&mut self, group_id: u64) -> Result<GroupInfo> {
+        let cmd = format!("/_join #{}", group_id);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::UserAcceptedGroupSent { group_info, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(group_info)
+    }
+
+    pub async fn api_remove_members(
+        &mut self,
+        group_id: u64,
+        group_member_ids: &[u64],
+        with_messages: bool,
+    ) -> Result<Vec<GroupMember>> {
+        let cmd = format!(
+            "/_remove #{} {} messages={}",
+            group_id,
+            join_ids(group_member_ids),
+            if with_messages { "on" } else { "off" }
+        );
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::UserDeletedMembers { members, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(members)
+    }
+
+    pub async fn api_member_role(
+        &mut self,
+        group_id: u64,
+        group_member_ids: &[u64],
+        role: GroupMemberRole,
+    ) -> Result<Vec<GroupMember>> {
+        check_role(&role)?;
+        let cmd = format!(
+            "/_member role #{} {} {}",
+            group_id,
+            join_ids(group_member_ids),
+            role
+        );
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::MembersRoleUser { members, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(members)
+    }
+
+    pub async fn api_block_member_for_all(
+        &mut self,
+        group_id: u64,
+        group_member_ids: &[u64],
+        blocked: bool,
+    ) -> Result<Vec<GroupMember>> {
+        let cmd = format!(
+            "/_block #{} {} blocked={}",
+            group_id,
+            join_ids(group_member_ids),
+            if blocked { "on" } else { "off" }
+        );
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::MembersBlockedForAllUser { members, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(members)
+    }
+
+    pub async fn api_create_group_link(
+        &mut self,
+        group_id: u64,
+        role: GroupMemberRole,
+    ) -> Result<ConnLinkContact> {
+        check_role(&role)?;
+        let cmd = format!("/_create link #{} {}", group_id, role);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupLinkCreated {
+            conn_link_contact, ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(conn_link_contact)
+    }
+
+    pub async fn api_get_group_link(&mut self, group_id: u64) -> Result<Option<ConnLinkContact>> {
+        let cmd = format!("/_get link #{}", group_id);
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::GroupLink {
+                conn_link_contact, ..
+            } => Ok(Some(conn_link_contact)),
+            ChatResponse::ChatCmdError {
+                chat_error:
+                    ChatError::ErrorStore {
+                        store_error: StoreError::GroupLinkNotFound { .. },
+                        ..
+                    },
+                ..
+            } => Ok(None),
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
+    // Sets the role given to members who join through the group link
+    pub async fn api_group_link_member_role(
+        &mut self,
+        group_id: u64,
+        role: GroupMemberRole,
+    ) -> Result<ConnLinkContact> {
+        check_role(&role)?;
+        let cmd = format!("/_set link role #{} {}", group_id, role);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupLink {
+            conn_link_contact, ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(conn_link_contact)
+    }
+
+    pub async fn api_delete_group_link(&mut self, group_id: u64) -> Result<()> {
+        let cmd = format!("/_delete link #{}", group_id);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupLinkDeleted { .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(())
+    }
+
+    pub async fn api_new_group(
+        &mut self,
+        user_id: u64,
+        profile: GroupProfile,
+    ) -> Result<GroupInfo> {
+        let profile_json = serde_json::to_string(&profile)?;
+        let cmd = format!("/_group {} {}", user_id, profile_json);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupCreated { group_info, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(group_info)
+    }
+
+    pub async fn api_update_group_profile(
+        &mut self,
+        group_id: u64,
+        profile: GroupProfile,
+    ) -> Result<GroupInfo> {
+        let profile_json = serde_json::to_string(&profile)?;
+        let cmd = format!("/_group_profile #{} {}", group_id, profile_json);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupUpdated { to_group, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(to_group)
+    }
+
+    pub async fn api_leave_group(&mut self, group_id: u64) -> Result<GroupInfo> {
+        let cmd = format!("/_leave #{}", group_id);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::LeftMemberUser { group_info, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(group_info)
+    }
+
+    pub async fn api_delete_group(&mut self, group_id: u64) -> Result<GroupInfo> {
+        let mode = ChatDeleteMode::Full { notify: true };
+        let cmd = format!("/_delete #{} {}", group_id, mode);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::GroupDeletedUser { group_info, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(group_info)
+    }
+
+    pub async fn api_clear_chat(&mut self, chat_ref: ChatRef) -> Result<ChatInfo> {
+        let cmd = format!("/_clear chat {}", chat_ref);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ChatCleared { chat_info, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(chat_info)
+    }
+
+    pub async fn api_delete_contact(
+        &mut self,
+        contact_id: u64,
+        mode: ChatDeleteMode,
+    ) -> Result<Contact> {
+        let cmd = format!("/_delete @{} {}", contact_id, mode);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ContactDeleted { contact, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(contact)
+    }
+
+    pub async fn api_set_contact_alias(&mut self, contact_id: u64, alias: &str) -> Result<Contact> {
+        let cmd = format!("/_set alias @{} {}", contact_id, alias);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ContactAliasUpdated { to_contact, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(to_contact)
+    }
+
+    // Returns the connection statistics of a contact, which include the SMP servers
+    // of its receive and send queues. `None` means the contact has no active connection
+    pub async fn api_contact_info(&mut self, contact_id: u64) -> Result<Option<ConnectionStats>> {
+        let cmd = format!("/_info @{}", contact_id);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ContactInfo {
+            connection_stats_, ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(connection_stats_)
+    }
+
+    pub async fn api_get_security_code(&mut self, contact_id: u64) -> Result<String> {
+        let cmd = format!("/_get code @{}", contact_id);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ContactCode {
+            connection_code, ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(connection_code)
+    }
+
+    // Compares `code` against the security code of the contact connection, marking
+    // the contact as verified if they match. Passing `None` clears the verification
+    pub async fn api_verify_contact(
+        &mut self,
+        contact_id: u64,
+        code: Option<&str>,
+    ) -> Result<bool> {
+        let cmd = match code {
+            Some(code) => format!("/_verify code @{} {}", contact_id, code),
+            None => format!("/_verify code @{}", contact_id),
+        };
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ConnectionVerified { verified, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(verified)
+    }
+
     pub async fn api_auto_accept(&mut self, on: bool) -> Result<()> {
         let onoff = if on { "on" } else { "off" };
         let cmd = format!("/auto_accept {}", onoff);
@@ -258,23 +727,393 @@ impl ChatClient {
         chat_type: ChatType,
         chat_id: u64,
         messages: Vec<ComposedMessage>,
+    ) -> Result<Vec<AChatItem>> {
+        self.send_messages(ChatRef::new(chat_type, chat_id), messages, false, None)
+            .await
+    }
+
+    // Sends disappearing messages, which are deleted `ttl` after being read.
+    // Passing `None` uses the timed messages preference of the chat
+    pub async fn api_send_timed_messages(
+        &mut self,
+        chat_ref: ChatRef,
+        messages: Vec<ComposedMessage>,
+        ttl: Option<Duration>,
+    ) -> Result<Vec<AChatItem>> {
+        self.send_messages(chat_ref, messages, false, ttl).await
+    }
+
+    async fn send_messages(
+        &mut self,
+        chat_ref: ChatRef,
+        messages: Vec<ComposedMessage>,
+        live: bool,
+        ttl: Option<Duration>,
     ) -> Result<Vec<AChatItem>> {
         let messages_json = serde_json::to_string(&messages)?;
         let cmd = format!(
-            "/_send {}{} live=off ttl=default json {}",
-            chat_type.to_string(),
-            chat_id,
+            "/_send {} live={} ttl={} json {}",
+            chat_ref,
+            if live { "on" } else { "off" },
+            format_ttl(ttl)?,
             messages_json
         );
         log::trace!("API send messages: {}", cmd);
         let resp = self.send_command(&cmd).await?;
         let ChatResponse::NewChatItems { chat_items, .. } = resp else {
-            bail!("The command response does not match the expected type");
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(chat_items)
+    }
+
+    // Starts a live message, which the recipients see being updated until it is finished
+    pub async fn api_start_live_message(
+        &mut self,
+        chat_ref: ChatRef,
+        text: &str,
+    ) -> Result<LiveMessage> {
+        let composed_message = ComposedMessage {
+            file_path: None,
+            quoted_item_id: None,
+            msg_content: MsgContent::text(text),
+            mentions: Mentions::default(),
+        };
+        let mut chat_items = self
+            .send_messages(chat_ref, vec![composed_message], true, None)
+            .await?;
+        let Some(chat_item) = chat_items.pop() else {
+            bail!("No chat item was created for the live message");
+        };
+
+        Ok(LiveMessage {
+            chat_ref,
+            item_id: chat_item.chat_item.meta.item_id,
+            text: text.to_owned(),
+        })
+    }
+
+    // Sets how long chat items are kept in the chat before being deleted
+    pub async fn api_set_chat_ttl(
+        &mut self,
+        user_id: u64,
+        chat_ref: ChatRef,
+        ttl: ChatTtl,
+    ) -> Result<()> {
+        let cmd = format!("/_ttl {} {} {}", user_id, chat_ref, format_chat_ttl(ttl)?);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::CmdOk { .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(())
+    }
+
+    // Sends a file from the local filesystem of the chat server.
+    // The transfer can be followed with the `SndFile*` events, which refer to the
+    // same chat item (`meta.item_id`) as the one returned here
+    pub async fn api_send_file(
+        &mut self,
+        chat_ref: ChatRef,
+        path: &str,
+        caption: &str,
+    ) -> Result<AChatItem> {
+        let composed_message = ComposedMessage {
+            file_path: Some(path.to_owned()),
+            quoted_item_id: None,
+            msg_content: MsgContent::File {
+                text: caption.to_owned(),
+                _unknown_fields: HashMap::new(),
+            },
+            mentions: Mentions::default(),
+        };
+
+        self.send_file_message(chat_ref, composed_message).await
+    }
+
+    // Sends an image, with a preview generated from the file at `path`.
+    // Note that the thumbnail is generated locally, so `path` must be accessible
+    // both from this process and from the chat server
+    pub async fn api_send_image(&mut self, chat_ref: ChatRef, path: &str) -> Result<AChatItem> {
+        let composed_message = ComposedMessage {
+            file_path: Some(path.to_owned()),
+            quoted_item_id: None,
+            msg_content: MsgContent::Image {
+                text: String::new(),
+                image: image_thumbnail(path)?,
+                _unknown_fields: HashMap::new(),
+            },
+            mentions: Mentions::default(),
+        };
+
+        self.send_file_message(chat_ref, composed_message).await
+    }
+
+    async fn send_file_message(
+        &mut self,
+        chat_ref: ChatRef,
+        composed_message: ComposedMessage,
+    ) -> Result<AChatItem> {
+        let mut chat_items = self
+            .send_messages(chat_ref, vec![composed_message], false, None)
+            .await?;
+        let Some(chat_item) = chat_items.pop() else {
+            bail!("No chat item was created for the file");
+        };
+
+        Ok(chat_item)
+    }
+
+    // Accepts an incoming file. Without a `destination`, the file is saved to
+    // the files folder of the chat server. `approve_relays` allows receiving
+    // the file from XFTP relays that are not among the configured servers
+    pub async fn api_receive_file(
+        &mut self,
+        file_id: u64,
+        destination: Option<&str>,
+        approve_relays: bool,
+    ) -> Result<AChatItem> {
+        let mut cmd = format!(
+            "/freceive {} approved_relays={}",
+            file_id,
+            if approve_relays { "on" } else { "off" }
+        );
+        if let Some(destination) = destination {
+            cmd = format!("{} {}", cmd, destination);
+        }
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::RcvFileAccepted { chat_item, .. } => Ok(chat_item),
+            ChatResponse::RcvFileAcceptedSndCancelled { .. } => {
+                bail!("The sender cancelled the file transfer");
+            }
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
+    // Accepts an incoming file and waits until it is received,
+    // returning the path of the file as reported by the chat server
+    pub async fn api_download_file(
+        &mut self,
+        file_id: u64,
+        destination: Option<&str>,
+        approve_relays: bool,
+        timeout: Option<Duration>,
+    ) -> Result<String> {
+        self.api_receive_file(file_id, destination, approve_relays)
+            .await?;
+
+        let resp = self
+            .wait_for_message(timeout, |resp| is_rcv_file_result(resp, file_id))
+            .await?;
+        match resp {
+            ChatResponse::RcvFileComplete { chat_item, .. } => {
+                let Some(file_source) = chat_item.chat_item.file.and_then(|file| file.file_source)
+                else {
+                    bail!("The received file has no local path");
+                };
+
+                Ok(file_source.file_path)
+            }
+            ChatResponse::RcvFileError { agent_error, .. } => {
+                bail!("Failed to receive file: {}", agent_error);
+            }
+            _ => {
+                bail!("The file transfer was cancelled");
+            }
+        }
+    }
+
+    pub async fn api_cancel_file(&mut self, file_id: u64) -> Result<()> {
+        let cmd = format!("/fcancel {}", file_id);
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::SndFileCancelled { .. } | ChatResponse::RcvFileCancelled { .. } => Ok(()),
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
+    pub async fn api_file_status(&mut self, file_id: u64) -> Result<CIFileStatus> {
+        let cmd = format!("/fstatus {}", file_id);
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::FileTransferStatusXFTP { chat_item, .. } => {
+                let Some(file) = chat_item.chat_item.file else {
+                    bail!("The chat item has no file");
+                };
+
+                Ok(file.file_status)
+            }
+            ChatResponse::FileTransferStatus {
+                file_transfer_status: (file_transfer, chunks),
+                ..
+            } => Ok(file_transfer.ci_file_status(&chunks)),
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
+    // Waits until a sent item is delivered or fails to be sent, returning its final status.
+    // Note that delivery to groups is only complete once every member received the item
+    pub async fn api_await_delivery(
+        &mut self,
+        chat_item: &AChatItem,
+        timeout: Option<Duration>,
+    ) -> Result<CIStatus> {
+        let status = &chat_item.chat_item.meta.item_status;
+        if status.is_delivered() || status.is_failed() {
+            return Ok(status.clone());
+        }
+
+        let item_id = chat_item.chat_item.meta.item_id;
+        let find_status = |chat_items: &[AChatItem]| {
+            chat_items
+                .iter()
+                .map(|chat_item| &chat_item.chat_item.meta)
+                .find(|meta| meta.item_id == item_id)
+                .map(|meta| meta.item_status.clone())
+                .filter(|status| status.is_delivered() || status.is_failed())
+        };
+        let resp = self
+            .wait_for_message(timeout, |resp| match resp {
+                ChatResponse::ChatItemsStatusesUpdated { chat_items, .. } => {
+                    find_status(chat_items).is_some()
+                }
+                _ => false,
+            })
+            .await?;
+        let ChatResponse::ChatItemsStatusesUpdated { chat_items, .. } = resp else {
+            bail!("The message does not match the expected type");
+        };
+        let Some(status) = find_status(&chat_items) else {
+            bail!("The message does not contain the status of the chat item");
+        };
+
+        Ok(status)
+    }
+
+    // Sends a message to the chat of `chat_item`, quoting it
+    pub async fn api_reply(
+        &mut self,
+        chat_item: &AChatItem,
+        msg_content: MsgContent,
+    ) -> Result<Vec<AChatItem>> {
+        let Some(chat_ref) = chat_item.chat_info.chat_ref() else {
+            bail!("The chat item does not belong to a direct or group chat");
+        };
+        let composed_message = ComposedMessage {
+            file_path: None,
+            quoted_item_id: Some(chat_item.chat_item.meta.item_id),
+            msg_content,
+            mentions: Mentions::default(),
+        };
+
+        self.api_send_messages(chat_ref.chat_type, chat_ref.chat_id, vec![composed_message])
+            .await
+    }
+
+    pub async fn api_forward_chat_items(
+        &mut self,
+        from: ChatRef,
+        item_ids: &[u64],
+        to: ChatRef,
+    ) -> Result<Vec<AChatItem>> {
+        let cmd = format!("/_forward {} {} {}", to, from, join_ids(item_ids));
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::NewChatItems { chat_items, .. } = resp else {
+            return Err(unexpected_response(resp));
         };
 
         Ok(chat_items)
     }
 
+    pub async fn api_update_chat_item(
+        &mut self,
+        chat_ref: ChatRef,
+        item_id: u64,
+        msg_content: MsgContent,
+        live: bool,
+    ) -> Result<AChatItem> {
+        let updated_message = UpdatedMessage {
+            msg_content,
+            mentions: Mentions::default(),
+        };
+        let message_json = serde_json::to_string(&updated_message)?;
+        let cmd = format!(
+            "/_update item {} {} live={} json {}",
+            chat_ref,
+            item_id,
+            if live { "on" } else { "off" },
+            message_json
+        );
+        let resp = self.send_command(&cmd).await?;
+        match resp {
+            ChatResponse::ChatItemUpdated { chat_item, .. }
+            | ChatResponse::ChatItemNotChanged { chat_item, .. } => Ok(chat_item),
+            resp => Err(unexpected_response(resp)),
+        }
+    }
+
+    pub async fn api_delete_chat_item(
+        &mut self,
+        chat_ref: ChatRef,
+        item_ids: &[u64],
+        mode: CIDeleteMode,
+    ) -> Result<Vec<ChatItemDeletion>> {
+        let cmd = format!("/_delete item {} {} {}", chat_ref, join_ids(item_ids), mode);
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ChatItemsDeleted {
+            chat_item_deletions,
+            ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(chat_item_deletions)
+    }
+
+    // Moderates (deletes for everyone) messages sent by other members of a group
+    pub async fn api_delete_member_chat_item(
+        &mut self,
+        group_id: u64,
+        item_ids: &[u64],
+    ) -> Result<Vec<ChatItemDeletion>> {
+        let cmd = format!("/_delete member item #{} {}", group_id, join_ids(item_ids));
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ChatItemsDeleted {
+            chat_item_deletions,
+            ..
+        } = resp
+        else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(chat_item_deletions)
+    }
+
+    pub async fn api_chat_item_reaction(
+        &mut self,
+        chat_ref: ChatRef,
+        item_id: u64,
+        add: bool,
+        reaction: MsgReaction,
+    ) -> Result<ACIReaction> {
+        let reaction_json = serde_json::to_string(&reaction)?;
+        let cmd = format!(
+            "/_reaction {} {} {} {}",
+            chat_ref,
+            item_id,
+            if add { "on" } else { "off" },
+            reaction_json
+        );
+        let resp = self.send_command(&cmd).await?;
+        let ChatResponse::ChatItemReaction { reaction, .. } = resp else {
+            return Err(unexpected_response(resp));
+        };
+
+        Ok(reaction)
+    }
+
     pub async fn api_send_text_message(
         &mut self,
         chat_type: ChatType,
@@ -284,11 +1123,8 @@ impl ChatClient {
         let composed_message = ComposedMessage {
             file_path: None,
             quoted_item_id: None,
-            msg_content: MsgContent::Text {
-                text: message.to_owned(),
-                _unknown_fields: HashMap::new(),
-            },
-            mentions: Mentions {},
+            msg_content: MsgContent::text(message),
+            mentions: Mentions::default(),
         };
 
         self.api_send_messages(chat_type, chat_id, vec![composed_message])
@@ -296,8 +1132,224 @@ impl ChatClient {
     }
 }
 
+// Error for a command response of an unexpected type.
+// If the chat server returned an error, it is passed on as a `ChatError`,
+// so that the callers can downcast it and check its `kind()`
+fn unexpected_response(resp: ChatResponse) -> anyhow::Error {
+    match resp {
+        ChatResponse::ChatCmdError { chat_error, .. }
+        | ChatResponse::ChatError { chat_error, .. } => chat_error.into(),
+        _ => anyhow!("The command response does not match the expected type"),
+    }
+}
+
+// Whether the message ends the reception of a file, successfully or not.
+// Errors and cancellations may come without a chat item (e.g. if it was deleted),
+// so they are matched by their file transfer instead
+fn is_rcv_file_result(resp: &ChatResponse, file_id: u64) -> bool {
+    match resp {
+        ChatResponse::RcvFileComplete { chat_item, .. } => chat_item
+            .chat_item
+            .file
+            .as_ref()
+            .is_some_and(|file| file.file_id == file_id),
+        ChatResponse::RcvFileError {
+            rcv_file_transfer, ..
+        }
+        | ChatResponse::RcvFileCancelled {
+            rcv_file_transfer, ..
+        } => rcv_file_transfer.file_id == file_id,
+        _ => false,
+    }
+}
+
+// Roles not known to this library can't be used in commands
+fn check_role(role: &GroupMemberRole) -> Result<()> {
+    if let GroupMemberRole::Unknown(role) = role {
+        bail!("Unknown group member role: {}", role);
+    }
+
+    Ok(())
+}
+
+// Formats a list of IDs as expected by the chat commands (e.g. `1,2,3`)
+fn join_ids(ids: &[u64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Handle to a live message started with `ChatClient::api_start_live_message`.
+// Every update replaces the whole text of the message. The handle doesn't borrow
+// the client, so other messages can still be received while streaming.
+// Note that the message stays live for the recipients until `finish` is called,
+// so dropping the handle without finishing it leaves the message unfinished
+#[derive(Debug)]
+pub struct LiveMessage {
+    chat_ref: ChatRef,
+    item_id: u64,
+    text: String,
+}
+
+impl LiveMessage {
+    pub fn chat_ref(&self) -> ChatRef {
+        self.chat_ref
+    }
+
+    pub fn item_id(&self) -> u64 {
+        self.item_id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub async fn update(&mut self, client: &mut ChatClient, text: &str) -> Result<AChatItem> {
+        text.clone_into(&mut self.text);
+        self.send_update(client, true).await
+    }
+
+    // Useful for streaming partial text (e.g. token by token)
+    pub async fn append(&mut self, client: &mut ChatClient, text: &str) -> Result<AChatItem> {
+        self.text.push_str(text);
+        self.send_update(client, true).await
+    }
+
+    // Sends the current text as the final version of the message
+    pub async fn finish(self, client: &mut ChatClient) -> Result<AChatItem> {
+        self.send_update(client, false).await
+    }
+
+    async fn send_update(&self, client: &mut ChatClient, live: bool) -> Result<AChatItem> {
+        client
+            .api_update_chat_item(
+                self.chat_ref,
+                self.item_id,
+                MsgContent::text(&self.text),
+                live,
+            )
+            .await
+    }
+}
+
+// Generates the JPEG preview of an image as a base64 data URI,
+// which is the format expected in `MsgContent::Image`
+fn image_thumbnail(path: &str) -> Result<String> {
+    const THUMBNAIL_SIZE: u32 = 128;
+
+    let image = image::open(path)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, 75).encode_image(&image.to_rgb8())?;
+
+    Ok(format!(
+        "data:image/jpg;base64,{}",
+        BASE64_STANDARD.encode(jpeg)
+    ))
+}
+
+// Formats a TTL in seconds as expected by the chat commands.
+// Partial seconds are rounded up, as a TTL of 0 is not valid
+fn format_ttl(ttl: Option<Duration>) -> Result<String> {
+    let Some(ttl) = ttl else {
+        return Ok("default".to_owned());
+    };
+    if ttl.is_zero() {
+        bail!("The TTL must be greater than zero");
+    }
+    let secs = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+
+    Ok(secs.to_string())
+}
+
+// Formats a chat TTL as expected by `/_ttl`, where 0 means never deleting the items
+fn format_chat_ttl(ttl: ChatTtl) -> Result<String> {
+    match ttl {
+        ChatTtl::Default => format_ttl(None),
+        ChatTtl::Never => Ok("0".to_owned()),
+        ChatTtl::After(ttl) => format_ttl(Some(ttl)),
+    }
+}
+
 impl Drop for ChatClient {
     fn drop(&mut self) {
         self.listener_handle.abort();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_ttl_in_seconds() {
+        assert_eq!(format_ttl(None).unwrap(), "default");
+        assert_eq!(
+            format_ttl(Some(Duration::from_secs(86400))).unwrap(),
+            "86400"
+        );
+        assert_eq!(format_ttl(Some(Duration::from_millis(1500))).unwrap(), "2");
+        assert_eq!(format_ttl(Some(Duration::from_millis(1))).unwrap(), "1");
+        assert!(format_ttl(Some(Duration::ZERO)).is_err());
+    }
+
+    #[test]
+    fn format_chat_ttl_in_seconds() {
+        assert_eq!(format_chat_ttl(ChatTtl::Default).unwrap(), "default");
+        assert_eq!(format_chat_ttl(ChatTtl::Never).unwrap(), "0");
+        assert_eq!(
+            format_chat_ttl(ChatTtl::After(Duration::from_secs(3600))).unwrap(),
+            "3600"
+        );
+        assert_eq!(
+            format_chat_ttl(ChatTtl::After(Duration::from_millis(500))).unwrap(),
+            "1"
+        );
+        // `ChatTtl::Never` must be used to stop deleting items
+        assert!(format_chat_ttl(ChatTtl::After(Duration::ZERO)).is_err());
+    }
+
+    #[test]
+    fn rcv_file_results() {
+        let payloads = [
+            include_str!("../tests/payloads/rcv_file_complete.json"),
+            // errors and cancellations without a chat item
+            include_str!("../tests/payloads/rcv_file_error.json"),
+            include_str!("../tests/payloads/rcv_file_cancelled.json"),
+        ];
+        for payload in payloads {
+            let resp: ChatResponse = serde_json::from_str(payload).unwrap();
+            assert!(is_rcv_file_result(&resp, 7));
+            assert!(!is_rcv_file_result(&resp, 8));
+        }
+    }
+
+    #[test]
+    fn command_errors_downcast_to_chat_error() {
+        let payload = include_str!("../tests/payloads/error_group_not_found.json");
+        let resp: ChatResponse = serde_json::from_str(payload).unwrap();
+        let err = unexpected_response(resp);
+        let chat_error = err.downcast_ref::<ChatError>().unwrap();
+        assert_eq!(chat_error.kind(), ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "store error: group 9 not found");
+
+        let payload = include_str!("../tests/payloads/error_agent_broker_timeout.json");
+        let resp: ChatResponse = serde_json::from_str(payload).unwrap();
+        let err = unexpected_response(resp);
+        assert!(err.downcast_ref::<ChatError>().unwrap().is_retryable());
+
+        // other responses are not chat errors
+        let resp: ChatResponse = serde_json::from_str(r#"{"type":"cmdOk"}"#).unwrap();
+        assert!(unexpected_response(resp)
+            .downcast_ref::<ChatError>()
+            .is_none());
+    }
+
+    #[test]
+    fn unknown_roles_are_rejected() {
+        assert!(check_role(&GroupMemberRole::Member).is_ok());
+        assert!(check_role(&GroupMemberRole::Owner).is_ok());
+        let role: GroupMemberRole = serde_json::from_str(r#""superadmin""#).unwrap();
+        assert!(check_role(&role).is_err());
+    }
+}
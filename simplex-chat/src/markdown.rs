@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::fmt;
+
+// Part of a message text, as parsed by the chat server.
+// Concatenating the `text` of all parts gives back the original message text
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FormattedText {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Format>,
+    pub text: String,
+}
+
+impl FormattedText {
+    pub fn new(text: &str, format: Format) -> Self {
+        Self {
+            format: Some(format),
+            text: text.to_owned(),
+        }
+    }
+
+    pub fn plain(text: &str) -> Self {
+        Self {
+            format: None,
+            text: text.to_owned(),
+        }
+    }
+}
+
+// Renders the part using the SimpleX markdown syntax (see `render_markdown`)
+impl fmt::Display for FormattedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = &self.text;
+        match &self.format {
+            Some(Format::Bold) => write!(f, "*{}*", text),
+            Some(Format::Italic) => write!(f, "_{}_", text),
+            Some(Format::StrikeThrough) => write!(f, "~{}~", text),
+            Some(Format::Snippet) => write!(f, "`{}`", text),
+            Some(Format::Secret) => write!(f, "#{}#", text),
+            Some(Format::Colored { color }) => match color.markdown_code() {
+                Some(code) => write!(f, "!{} {}!", code, text),
+                None => write!(f, "{}", text),
+            },
+            Some(Format::Mention { member_name }) => {
+                // the server reads an unquoted name up to a space and drops the
+                // punctuation at its end; quoted names can't contain quotes
+                let needs_quotes = member_name.contains(char::is_whitespace)
+                    || !member_name.ends_with(char::is_alphanumeric);
+                if needs_quotes && !member_name.contains('\'') {
+                    write!(f, "@'{}'", member_name)
+                } else {
+                    write!(f, "@{}", member_name)
+                }
+            }
+            // links, emails and phones are detected by the server in plain text
+            _ => write!(f, "{}", text),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum Format {
+    Bold,
+    Italic,
+    StrikeThrough,
+    Snippet,
+    Secret,
+    Colored {
+        color: Color,
+    },
+    Uri,
+    SimplexLink {
+        link_type: SimplexLinkType,
+        simplex_uri: String,
+        smp_hosts: Vec<String>,
+    },
+    Mention {
+        member_name: String,
+    },
+    Email,
+    Phone,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    // Code of the color in markdown (`!1 red text!`), if it can be used there
+    pub fn markdown_code(&self) -> Option<u8> {
+        match self {
+            Self::Red => Some(1),
+            Self::Green => Some(2),
+            Self::Blue => Some(3),
+            Self::Yellow => Some(4),
+            Self::Cyan => Some(5),
+            Self::Magenta => Some(6),
+            Self::Black | Self::White => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SimplexLinkType {
+    Contact,
+    Invitation,
+    Group,
+    Channel,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+// Renders formatted text as a SimpleX markdown message.
+// SimpleX markdown has no escape syntax, so plain parts are written as is
+// and markdown characters in them (e.g. `2*3*4`) are parsed as formatting
+// by the server when the message is sent
+pub fn render_markdown(parts: &[FormattedText]) -> String {
+    parts.iter().map(|part| part.to_string()).collect()
+}
+
+// Links (including SimpleX links) found in formatted text
+pub fn extract_links(parts: &[FormattedText]) -> Vec<&str> {
+    parts
+        .iter()
+        .filter_map(|part| match &part.format {
+            Some(Format::Uri) => Some(part.text.as_str()),
+            Some(Format::SimplexLink { simplex_uri, .. }) => Some(simplex_uri.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Names of the members mentioned in formatted text
+pub fn extract_mentions(parts: &[FormattedText]) -> Vec<&str> {
+    parts
+        .iter()
+        .filter_map(|part| match &part.format {
+            Some(Format::Mention { member_name }) => Some(member_name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_formats() {
+        let cases = [
+            (Format::Bold, "*text*"),
+            (Format::Italic, "_text_"),
+            (Format::StrikeThrough, "~text~"),
+            (Format::Snippet, "`text`"),
+            (Format::Secret, "#text#"),
+            (Format::Colored { color: Color::Red }, "!1 text!"),
+            (
+                Format::Colored {
+                    color: Color::Black,
+                },
+                "text",
+            ),
+            (Format::Uri, "text"),
+            (Format::Email, "text"),
+            (Format::Phone, "text"),
+            (
+                Format::Mention {
+                    member_name: "alice".to_owned(),
+                },
+                "@alice",
+            ),
+            (
+                Format::Mention {
+                    member_name: "alice smith".to_owned(),
+                },
+                "@'alice smith'",
+            ),
+            (
+                Format::Mention {
+                    member_name: "o'neil".to_owned(),
+                },
+                "@o'neil",
+            ),
+            (
+                Format::Mention {
+                    member_name: "bob.".to_owned(),
+                },
+                "@'bob.'",
+            ),
+        ];
+        for (format, expected) in cases {
+            assert_eq!(FormattedText::new("text", format).to_string(), expected);
+        }
+        assert_eq!(FormattedText::plain("text").to_string(), "text");
+    }
+
+    #[test]
+    fn color_codes() {
+        let codes = [
+            (Color::Red, Some(1)),
+            (Color::Green, Some(2)),
+            (Color::Blue, Some(3)),
+            (Color::Yellow, Some(4)),
+            (Color::Cyan, Some(5)),
+            (Color::Magenta, Some(6)),
+            (Color::Black, None),
+            (Color::White, None),
+        ];
+        for (color, code) in codes {
+            assert_eq!(color.markdown_code(), code);
+        }
+    }
+
+    #[test]
+    fn render_parts() {
+        let parts = [
+            FormattedText::plain("hello "),
+            FormattedText::new("world", Format::Bold),
+            FormattedText::plain(", "),
+            FormattedText::new(
+                "green",
+                Format::Colored {
+                    color: Color::Green,
+                },
+            ),
+        ];
+        assert_eq!(render_markdown(&parts), "hello *world*, !2 green!");
+
+        // plain text is not escaped
+        assert_eq!(render_markdown(&[FormattedText::plain("2*3*4")]), "2*3*4");
+    }
+}
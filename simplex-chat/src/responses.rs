@@ -1,3 +1,4 @@
+pub use crate::errors::*;
 pub use crate::types::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -6,6 +7,7 @@ use std::collections::HashMap;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[allow(clippy::large_enum_variant)]
 pub enum ChatResponse {
     ActiveUser {
         user: User,
@@ -13,6 +15,28 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    ApiChat {
+        user: User,
+        chat: Chat,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nav_info: Option<JsonValue>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    CallEnded {
+        user: User,
+        contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    CallInvitation {
+        call_invitation: RcvCallInvitation,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     ChatError {
         #[serde(skip_serializing_if = "Option::is_none")]
         user_: Option<User>,
@@ -21,6 +45,13 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    // errors that are not related to a command, e.g. of the connections
+    ChatErrors {
+        chat_errors: Vec<ChatError>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     ChatCmdError {
         #[serde(skip_serializing_if = "Option::is_none")]
         user_: Option<User>,
@@ -29,6 +60,51 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    ChatCleared {
+        user: User,
+        chat_info: ChatInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ChatItemNotChanged {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ChatItemReaction {
+        user: User,
+        added: bool,
+        reaction: ACIReaction,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ChatItemsStatusesUpdated {
+        user: User,
+        chat_items: Vec<AChatItem>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ChatItemUpdated {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ChatItemsDeleted {
+        user: User,
+        chat_item_deletions: Vec<ChatItemDeletion>,
+        by_user: bool,
+        timed: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     ChatRunning {
         #[serde(flatten)]
         #[serde(skip_serializing_if = "HashMap::is_empty")]
@@ -51,6 +127,36 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    CmdOk {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user_: Option<User>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ConnectionVerified {
+        user: User,
+        verified: bool,
+        expected_code: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactAliasUpdated {
+        user: User,
+        to_contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactCode {
+        user: User,
+        contact: Contact,
+        connection_code: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     ContactConnected {
         contact: Contact,
         user: User,
@@ -60,6 +166,165 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    ContactConnecting {
+        user: User,
+        contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactDeleted {
+        user: User,
+        contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactDeletedByContact {
+        user: User,
+        contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactInfo {
+        user: User,
+        contact: Contact,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        connection_stats_: Option<ConnectionStats>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        custom_user_profile: Option<Profile>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactSndReady {
+        user: User,
+        contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactSubError {
+        user: User,
+        contact: Contact,
+        chat_error: ChatError,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactSubSummary {
+        user: User,
+        contact_subscriptions: Vec<ContactSubStatus>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactUpdated {
+        user: User,
+        from_contact: Contact,
+        to_contact: Contact,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    // the connections to the SMP server were lost
+    ContactsDisconnected {
+        server: String,
+        contact_refs: Vec<ContactRef>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ContactsSubscribed {
+        server: String,
+        contact_refs: Vec<ContactRef>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    DeletedMember {
+        user: User,
+        group_info: GroupInfo,
+        by_member: GroupMember,
+        deleted_member: GroupMember,
+        with_messages: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    DeletedMemberUser {
+        user: User,
+        group_info: GroupInfo,
+        member: GroupMember,
+        with_messages: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    // status of a file transferred over SMP, with the number of chunks
+    // transferred (see `FileTransfer::ci_file_status`)
+    FileTransferStatus {
+        user: User,
+        file_transfer_status: (FileTransfer, Vec<u64>),
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    FileTransferStatusXFTP {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupCreated {
+        user: User,
+        group_info: GroupInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupDeleted {
+        user: User,
+        group_info: GroupInfo,
+        member: GroupMember,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupDeletedUser {
+        user: User,
+        group_info: GroupInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupLink {
+        user: User,
+        group_info: GroupInfo,
+        conn_link_contact: ConnLinkContact,
+        member_role: GroupMemberRole,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupLinkCreated {
+        user: User,
+        group_info: GroupInfo,
+        conn_link_contact: ConnLinkContact,
+        member_role: GroupMemberRole,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    GroupLinkDeleted {
+        user: User,
+        group_info: GroupInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     GroupMembers {
         user: User,
         group: Group,
@@ -67,6 +332,107 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    GroupUpdated {
+        user: User,
+        from_group: GroupInfo,
+        to_group: GroupInfo,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        member_: Option<GroupMember>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    HostConnected {
+        protocol: String,
+        transport_host: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    HostDisconnected {
+        protocol: String,
+        transport_host: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    ItemsReadForChat {
+        user: User,
+        chat_info: ChatInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    JoinedGroupMember {
+        user: User,
+        group_info: GroupInfo,
+        member: GroupMember,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    LeftMember {
+        user: User,
+        group_info: GroupInfo,
+        member: GroupMember,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    LeftMemberUser {
+        user: User,
+        group_info: GroupInfo,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    MemberBlockedForAll {
+        user: User,
+        group_info: GroupInfo,
+        by_member: GroupMember,
+        member: GroupMember,
+        blocked: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    MemberRole {
+        user: User,
+        group_info: GroupInfo,
+        by_member: GroupMember,
+        member: GroupMember,
+        from_role: GroupMemberRole,
+        to_role: GroupMemberRole,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    MembersBlockedForAllUser {
+        user: User,
+        group_info: GroupInfo,
+        members: Vec<GroupMember>,
+        blocked: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    MembersRoleUser {
+        user: User,
+        group_info: GroupInfo,
+        members: Vec<GroupMember>,
+        to_role: GroupMemberRole,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    MessageError {
+        user: User,
+        severity: String,
+        error_message: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     NewChatItems {
         user: User,
         chat_items: Vec<AChatItem>,
@@ -83,6 +449,116 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    RcvFileAccepted {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvFileAcceptedSndCancelled {
+        user: User,
+        rcv_file_transfer: RcvFileTransfer,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvFileCancelled {
+        user: User,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_: Option<AChatItem>,
+        rcv_file_transfer: RcvFileTransfer,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvFileComplete {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvFileError {
+        user: User,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_: Option<AChatItem>,
+        agent_error: AgentError,
+        rcv_file_transfer: RcvFileTransfer,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvFileStart {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SentGroupInvitation {
+        user: User,
+        group_info: GroupInfo,
+        contact: Contact,
+        member: GroupMember,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SndFileCancelled {
+        user: User,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_: Option<AChatItem>,
+        file_transfer_meta: FileTransferMeta,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SndFileCompleteXFTP {
+        user: User,
+        chat_item: AChatItem,
+        file_transfer_meta: FileTransferMeta,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SndFileError {
+        user: User,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_: Option<AChatItem>,
+        file_transfer_meta: FileTransferMeta,
+        error_message: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SndFileProgressXFTP {
+        user: User,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_: Option<AChatItem>,
+        file_transfer_meta: FileTransferMeta,
+        sent_size: u64,
+        total_size: u64,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    SndFileStart {
+        user: User,
+        chat_item: AChatItem,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    UserAcceptedGroupSent {
+        user: User,
+        group_info: GroupInfo,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        host_contact: Option<Contact>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     UserContactLinkCreated {
         user: User,
         conn_link_contact: ConnLinkContact,
@@ -90,6 +566,37 @@ pub enum ChatResponse {
         #[serde(skip_serializing_if = "HashMap::is_empty")]
         _unknown_fields: HashMap<String, JsonValue>,
     },
+    UserDeletedMembers {
+        user: User,
+        group_info: GroupInfo,
+        members: Vec<GroupMember>,
+        with_messages: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    UserJoinedGroup {
+        user: User,
+        group_info: GroupInfo,
+        host_member: GroupMember,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    UserProfileNoChange {
+        user: User,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    UserProfileUpdated {
+        user: User,
+        from_profile: Profile,
+        to_profile: Profile,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
     UsersList {
         users: Vec<UserInfo>,
         #[serde(flatten)]
@@ -0,0 +1,90 @@
+// Serde helpers for the timestamps sent by the chat server.
+// They are usually RFC 3339 in UTC, but the precision of the fractional seconds
+// varies, and some versions omit the offset or use a space as the separator
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+pub fn parse(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(s) {
+        return Some(ts.with_timezone(&Utc));
+    }
+
+    // Timestamps without an offset are assumed to be in UTC
+    let naive = s.trim_end_matches(" UTC").trim_end_matches('Z');
+    NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+        .map(|ts| ts.and_utc())
+}
+
+pub fn serialize<S: Serializer>(ts: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    ts.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse(&s).ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {}", s)))
+}
+
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        ts: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => super::parse(&s)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {}", s))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse_timestamps() {
+        let ts = Utc.with_ymd_and_hms(2025, 6, 2, 10, 15, 42).unwrap();
+        let ts_micros = ts + chrono::Duration::microseconds(123456);
+        let cases = [
+            ("2025-06-02T10:15:42.123456Z", ts_micros),
+            ("2025-06-02T10:15:42Z", ts),
+            ("2025-06-02T12:15:42+02:00", ts),
+            ("2025-06-02T10:15:42.123456", ts_micros),
+            ("2025-06-02T10:15:42", ts),
+            ("2025-06-02 10:15:42.123456", ts_micros),
+            ("2025-06-02 10:15:42 UTC", ts),
+            ("2025-06-02 10:15:42.123456 UTC", ts_micros),
+        ];
+        for (s, expected) in cases {
+            assert_eq!(parse(s), Some(expected), "{}", s);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_timestamps() {
+        for s in [
+            "",
+            "yesterday",
+            "2025-06-02",
+            "2025-06-02T10:15",
+            "02/06/2025 10:15:42",
+        ] {
+            assert_eq!(parse(s), None, "{}", s);
+        }
+    }
+}
@@ -1,7 +1,12 @@
+use crate::errors::{ChatError, SndError};
+use crate::markdown::{Format, FormattedText};
+use crate::timestamp;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -14,6 +19,8 @@ pub struct LocalProfile {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contact_link: Option<String>,
     pub local_alias: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<Preferences>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
@@ -50,17 +57,47 @@ pub struct UserInfo {
 pub struct Contact {
     pub contact_id: u64,
     pub local_display_name: String,
-    // profile: Profile,
+    pub profile: LocalProfile,
     pub active_conn: Option<Connection>,
     pub contact_status: ContactStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub via_group: Option<u64>,
-    // created_at: Date,
+    pub chat_settings: ChatSettings,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "timestamp")]
+    pub updated_at: DateTime<Utc>,
+    // time of the last activity in the chat
+    #[serde(default, with = "timestamp::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_ts: Option<DateTime<Utc>>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatSettings {
+    pub enable_ntfs: MsgFilter,
+    // None uses the default of the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_rcpts: Option<bool>,
+    pub favorite: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MsgFilter {
+    // muted
+    None,
+    #[default]
+    All,
+    Mentions,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum ContactStatus {
@@ -72,6 +109,7 @@ pub enum ContactStatus {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 #[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
 pub enum ChatInfo {
     Direct {
         contact: Contact,
@@ -95,6 +133,17 @@ pub enum ChatInfo {
     Unknown(JsonValue),
 }
 
+impl ChatInfo {
+    // Reference to the chat that can be used in commands, if there is one
+    pub fn chat_ref(&self) -> Option<ChatRef> {
+        match self {
+            Self::Direct { contact, .. } => Some(ChatRef::direct(contact.contact_id)),
+            Self::Group { group_info, .. } => Some(ChatRef::group(group_info.group_id)),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Group {
@@ -112,7 +161,15 @@ pub struct GroupInfo {
     pub local_display_name: String,
     pub group_profile: GroupProfile,
     pub membership: GroupMember,
-    // pub created_at: Date, // TODO: Pick date type
+    pub chat_settings: ChatSettings,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "timestamp")]
+    pub updated_at: DateTime<Utc>,
+    // time of the last activity in the chat
+    #[serde(default, with = "timestamp::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_ts: Option<DateTime<Utc>>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
@@ -124,56 +181,293 @@ pub struct GroupProfile {
     pub display_name: String,
     pub full_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
+impl GroupProfile {
+    pub fn new(display_name: &str, full_name: &str) -> Self {
+        Self {
+            display_name: display_name.to_owned(),
+            full_name: full_name.to_owned(),
+            description: None,
+            image: None,
+            _unknown_fields: HashMap::new(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupMember {
     pub group_member_id: u64,
     pub member_id: String,
     pub member_role: GroupMemberRole,
+    pub member_category: GroupMemberCategory,
+    pub member_status: GroupMemberStatus,
     pub local_display_name: String,
     pub member_profile: Profile,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub member_contact_id: Option<u64>,
     pub active_conn: Option<Connection>,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "timestamp")]
+    pub updated_at: DateTime<Utc>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl GroupMember {
+    // Whether the member can delete messages of other members and block them
+    pub fn can_moderate(&self) -> bool {
+        self.member_role >= GroupMemberRole::Moderator
+    }
+
+    // Whether the member can add and remove members and change their roles
+    pub fn is_admin(&self) -> bool {
+        self.member_role >= GroupMemberRole::Admin
+    }
+
+    // Whether the member is (or is becoming) part of the group
+    pub fn is_active(&self) -> bool {
+        self.member_status.is_active()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GroupMemberStatus {
+    // rejected by an admin when joining
+    #[serde(rename = "rejected")]
+    Rejected,
+    // removed by another member
+    #[serde(rename = "removed")]
+    Removed,
+    #[serde(rename = "left")]
+    Left,
+    // the group was deleted
+    #[serde(rename = "deleted")]
+    GroupDeleted,
+    #[serde(rename = "invited")]
+    Invited,
+    #[serde(rename = "pending_approval")]
+    PendingApproval,
+    #[serde(rename = "pending_review")]
+    PendingReview,
+    #[serde(rename = "introduced")]
+    Introduced,
+    #[serde(rename = "intro-inv")]
+    IntroInvited,
+    #[serde(rename = "accepted")]
+    Accepted,
+    #[serde(rename = "announced")]
+    Announced,
+    #[serde(rename = "connected")]
+    Connected,
+    #[serde(rename = "complete")]
+    Complete,
+    #[serde(rename = "creator")]
+    Creator,
+    // also used by the server for members only known from forwarded messages
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl GroupMemberStatus {
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            Self::Introduced
+                | Self::IntroInvited
+                | Self::Accepted
+                | Self::Announced
+                | Self::Connected
+                | Self::Complete
+                | Self::Creator
+        )
+    }
+}
+
+// How the current user is related to the member
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GroupMemberCategory {
+    // the current user
+    #[serde(rename = "user")]
+    User,
+    // invited by the current user
+    #[serde(rename = "invitee")]
+    Invitee,
+    // invited the current user
+    #[serde(rename = "host")]
+    Host,
+    // joined before the current user
+    #[serde(rename = "pre")]
+    PreMember,
+    // joined after the current user
+    #[serde(rename = "post")]
+    PostMember,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+// Roles are ordered by their permissions, e.g. `Admin > Moderator`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum GroupMemberRole {
+    Observer,
     // role used for unknown profiles in group
     // (e.g. forwarded messages from member no longer in the group)
     Author,
-    Observer,
     Member,
+    Moderator,
     Admin,
     Owner,
     #[serde(untagged)]
     Unknown(JsonValue),
 }
 
+impl GroupMemberRole {
+    fn rank(&self) -> Option<u8> {
+        match self {
+            Self::Observer => Some(0),
+            Self::Author => Some(1),
+            Self::Member => Some(2),
+            Self::Moderator => Some(3),
+            Self::Admin => Some(4),
+            Self::Owner => Some(5),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+// Unknown roles can't be compared to any other role
+impl PartialOrd for GroupMemberRole {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.rank(), other.rank()) {
+            (Some(rank), Some(other_rank)) => Some(rank.cmp(&other_rank)),
+            _ if self == other => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GroupMemberRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role = match self {
+            Self::Author => "author",
+            Self::Observer => "observer",
+            Self::Member => "member",
+            Self::Moderator => "moderator",
+            Self::Admin => "admin",
+            Self::Owner => "owner",
+            Self::Unknown(role) => role.as_str().unwrap_or_default(),
+        };
+        write!(f, "{}", role)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Profile {
-    display_name: String,
-    full_name: String,
+    pub display_name: String,
+    pub full_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>, // Base64 string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<Preferences>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+impl Profile {
+    pub fn new(display_name: &str, full_name: &str) -> Self {
+        Self {
+            display_name: display_name.to_owned(),
+            full_name: full_name.to_owned(),
+            image: None,
+            contact_link: None,
+            preferences: None,
+            _unknown_fields: HashMap::new(),
+        }
+    }
+
+    pub fn with_image(mut self, image: &str) -> Self {
+        self.image = Some(image.to_owned());
+        self
+    }
+
+    pub fn with_contact_link(mut self, contact_link: &str) -> Self {
+        self.contact_link = Some(contact_link.to_owned());
+        self
+    }
+
+    pub fn with_preferences(mut self, preferences: Preferences) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+}
+
+// Preferences of a user for the chat features of their contacts.
+// Unset preferences use the defaults of the user
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preferences {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timed_messages: Option<TimedMessagesPreference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_delete: Option<FeaturePreference>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    image: Option<String>,
+    pub reactions: Option<FeaturePreference>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    contact_link: Option<String>,
+    pub voice: Option<FeaturePreference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calls: Option<FeaturePreference>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimedMessagesPreference {
+    pub allow: FeatureAllowed,
+    // default TTL of the messages, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturePreference {
+    pub allow: FeatureAllowed,
+}
+
+impl FeaturePreference {
+    pub fn new(allow: FeatureAllowed) -> Self {
+        Self { allow }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum FeatureAllowed {
+    // allowed whenever the contact allows it too
+    Always,
+    // allowed if the contact allows it
+    Yes,
+    No,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AChatItem {
@@ -189,41 +483,39 @@ pub struct AChatItem {
 pub struct ChatItem {
     pub chat_dir: Direction,
     pub meta: Meta,
-    // pub content: CIContent,
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub formatted_text: Option<Vec<FormattedText>>,
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub quoted_item: Option<CIQuote>
+    pub content: CIContent,
+    // None if the text has no formatting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted_text: Option<Vec<FormattedText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quoted_item: Option<CIQuote>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<CIReactionCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<CIFile>,
+    // members mentioned in the item, by the name used in the text
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub mentions: HashMap<String, CIMention>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
-#[serde(tag = "type")]
-pub enum Direction {
-    DirectSnd,
-    DirectRcv,
-    GroupSnd,
-    GroupRcv {
-        group_member: GroupMember,
-    },
-    #[serde(untagged)]
-    Unknown(JsonValue),
+impl ChatItem {
+    pub fn is_mentioning_me(&self) -> bool {
+        self.meta.user_mention
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct Meta {
-    pub item_id: u64,
-    pub item_ts: DateTime<Utc>,
-    pub item_text: String,
-    // item_status: CIStatus,
-    // created_at: Date,
-    // pub item_deleted: bool,
-    pub item_edited: bool,
-    pub editable: bool,
+pub struct CIMention {
+    pub member_id: String,
+    // None if the member is not known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_ref: Option<CIMentionMember>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
@@ -231,126 +523,1156 @@ pub struct Meta {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct Chat {
-    pub chat_info: ChatInfo,
-    // chat_items: Vec<ChatItem>,
-    // chat_stats: ChatStats,
+pub struct CIMentionMember {
+    pub group_member_id: u64,
+    pub display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_alias: Option<String>,
+    pub member_role: GroupMemberRole,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ContactLink {
-    pub address_settings: AddressSettings,
-    pub conn_link_contact: ConnLinkContact,
-    pub short_link_data_set: bool,
-    pub user_contact_link_id: i64,
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
+pub enum CIContent {
+    SndMsgContent {
+        msg_content: MsgContent,
+    },
+    RcvMsgContent {
+        msg_content: MsgContent,
+    },
+    SndDeleted {
+        delete_mode: CIDeleteMode,
+    },
+    RcvDeleted {
+        delete_mode: CIDeleteMode,
+    },
+    SndCall {
+        status: CICallStatus,
+        duration: u64, // seconds
+    },
+    RcvCall {
+        status: CICallStatus,
+        duration: u64, // seconds
+    },
+    RcvIntegrityError {
+        msg_error: JsonValue,
+    },
+    RcvDecryptionError {
+        msg_decrypt_error: JsonValue,
+        msg_count: u64,
+    },
+    RcvGroupInvitation {
+        group_invitation: CIGroupInvitation,
+        member_role: GroupMemberRole,
+    },
+    SndGroupInvitation {
+        group_invitation: CIGroupInvitation,
+        member_role: GroupMemberRole,
+    },
+    RcvDirectEvent {
+        rcv_direct_event: JsonValue,
+    },
+    RcvGroupEvent {
+        rcv_group_event: RcvGroupEvent,
+    },
+    SndGroupEvent {
+        snd_group_event: SndGroupEvent,
+    },
+    RcvConnEvent {
+        rcv_conn_event: JsonValue,
+    },
+    SndConnEvent {
+        snd_conn_event: JsonValue,
+    },
+    RcvChatFeature {
+        feature: ChatFeature,
+        enabled: JsonValue,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+    },
+    SndChatFeature {
+        feature: ChatFeature,
+        enabled: JsonValue,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+    },
+    RcvChatPreference {
+        feature: ChatFeature,
+        allowed: FeatureAllowed,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+    },
+    SndChatPreference {
+        feature: ChatFeature,
+        allowed: FeatureAllowed,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+    },
+    RcvGroupFeature {
+        group_feature: GroupFeature,
+        preference: JsonValue,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        member_role_: Option<GroupMemberRole>,
+    },
+    SndGroupFeature {
+        group_feature: GroupFeature,
+        preference: JsonValue,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        param: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        member_role_: Option<GroupMemberRole>,
+    },
+    RcvChatFeatureRejected {
+        feature: ChatFeature,
+    },
+    RcvGroupFeatureRejected {
+        group_feature: GroupFeature,
+    },
+    SndModerated,
+    RcvModerated,
+    RcvBlocked,
+    SndDirectE2EEInfo {
+        e2ee_info: JsonValue,
+    },
+    RcvDirectE2EEInfo {
+        e2ee_info: JsonValue,
+    },
+    SndGroupE2EEInfo {
+        e2ee_info: JsonValue,
+    },
+    RcvGroupE2EEInfo {
+        e2ee_info: JsonValue,
+    },
+    #[serde(rename = "invalidJSON")]
+    InvalidJson {
+        json: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl CIContent {
+    // Content of the item if it is a sent or received message
+    pub fn msg_content(&self) -> Option<&MsgContent> {
+        match self {
+            Self::SndMsgContent { msg_content } | Self::RcvMsgContent { msg_content } => {
+                Some(msg_content)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct AddressSettings {
-    pub auto_accept: AutoAccept,
-    pub business_address: bool,
+pub enum CICallStatus {
+    Pending,
+    Missed,
+    Rejected,
+    Accepted,
+    Negotiated,
+    Progress,
+    Ended,
+    Error,
+    #[serde(untagged)]
+    Unknown(JsonValue),
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ConnLinkContact {
-    pub conn_full_link: String,
-    pub conn_short_link: String,
+pub struct CIGroupInvitation {
+    pub group_id: u64,
+    pub group_member_id: u64,
+    pub local_display_name: String,
+    pub group_profile: GroupProfile,
+    pub status: CIGroupInvitationStatus,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct AutoAccept {
-    pub accept_incognito: bool,
+pub enum CIGroupInvitationStatus {
+    Pending,
+    Accepted,
+    Rejected,
+    Expired,
+    #[serde(untagged)]
+    Unknown(JsonValue),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(
-    tag = "type",
-    rename_all = "camelCase",
-    rename_all_fields = "camelCase"
-)]
-pub enum MsgContent {
-    Text {
-        text: String,
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
+pub enum RcvGroupEvent {
+    MemberAdded {
+        group_member_id: u64,
+        profile: Profile,
     },
-    Link {
-        text: String,
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+    MemberConnected,
+    MemberAccepted {
+        group_member_id: u64,
+        profile: Profile,
     },
-    Image {
-        image: String, // Base64 string
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+    UserAccepted,
+    MemberLeft,
+    MemberRole {
+        group_member_id: u64,
+        profile: Profile,
+        role: GroupMemberRole,
     },
-    File {
-        text: String,
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+    MemberBlocked {
+        group_member_id: u64,
+        profile: Profile,
+        blocked: bool,
+    },
+    UserRole {
+        role: GroupMemberRole,
+    },
+    MemberDeleted {
+        group_member_id: u64,
+        profile: Profile,
     },
+    UserDeleted,
+    GroupDeleted,
+    GroupUpdated {
+        group_profile: GroupProfile,
+    },
+    InvitedViaGroupLink,
+    MemberCreatedContact,
+    MemberProfileUpdated {
+        from_profile: Profile,
+        to_profile: Profile,
+    },
+    NewMemberPendingReview,
+    #[serde(untagged)]
     Unknown(JsonValue),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 #[serde(tag = "type")]
-pub enum ChatError {
-    Error {
-        error_type: ChatErrorType,
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+pub enum SndGroupEvent {
+    MemberRole {
+        group_member_id: u64,
+        profile: Profile,
+        role: GroupMemberRole,
     },
-    ErrorAgent {
-        agent_error: JsonValue,
-        #[serde(flatten)]
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        _unknown_fields: HashMap<String, JsonValue>,
+    MemberBlocked {
+        group_member_id: u64,
+        profile: Profile,
+        blocked: bool,
+    },
+    UserRole {
+        role: GroupMemberRole,
+    },
+    MemberDeleted {
+        group_member_id: u64,
+        profile: Profile,
+    },
+    UserLeft,
+    GroupUpdated {
+        group_profile: GroupProfile,
     },
-    ErrorStore {
-        store_error: JsonValue,
+    MemberAccepted {
+        group_member_id: u64,
+        profile: Profile,
     },
+    UserPendingReview,
     #[serde(untagged)]
     Unknown(JsonValue),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
-pub enum ChatErrorType {
-    NoActiveUser,
-    ActiveUserExists,
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChatFeature {
+    TimedMessages,
+    FullDelete,
+    Reactions,
+    Voice,
+    Calls,
     #[serde(untagged)]
     Unknown(JsonValue),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct Connection {
-    pub conn_id: u64,
+pub enum GroupFeature {
+    TimedMessages,
+    DirectMessages,
+    FullDelete,
+    Reactions,
+    Voice,
+    Files,
+    SimplexLinks,
+    Reports,
+    History,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CIQuote {
+    // direction of the quoted item, None if it could not be determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_dir: Option<Direction>,
+    // ID of the quoted item, None if it is not available locally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<u64>,
+    #[serde(with = "timestamp")]
+    pub sent_at: DateTime<Utc>,
+    pub content: MsgContent,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum MsgReaction {
+    Emoji {
+        emoji: String,
+    },
+    // reactions not supported by the server are kept as they were received
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl MsgReaction {
+    pub fn emoji(emoji: &str) -> Self {
+        Self::Emoji {
+            emoji: emoji.to_owned(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CIReactionCount {
+    pub reaction: MsgReaction,
+    pub user_reacted: bool,
+    pub total_reacted: u64,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ACIReaction {
+    pub chat_info: ChatInfo,
+    pub chat_reaction: CIReaction,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CIReaction {
+    // who reacted
+    pub chat_dir: Direction,
+    // item that was reacted to
+    pub chat_item: ChatItem,
+    #[serde(with = "timestamp")]
+    pub sent_at: DateTime<Utc>,
+    pub reaction: MsgReaction,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum Direction {
+    DirectSnd,
+    DirectRcv,
+    GroupSnd,
+    GroupRcv {
+        group_member: Box<GroupMember>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    pub item_id: u64,
+    #[serde(with = "timestamp")]
+    pub item_ts: DateTime<Utc>,
+    pub item_text: String,
+    pub item_status: CIStatus,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "timestamp")]
+    pub updated_at: DateTime<Utc>,
+    // pub item_deleted: bool,
+    pub item_edited: bool,
+    // whether the current user is mentioned in the item
+    #[serde(default)]
+    pub user_mention: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_forwarded: Option<CIForwardedFrom>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_timed: Option<CITimed>,
+    pub editable: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum CIStatus {
+    // sent items
+    SndNew,
+    SndSent {
+        snd_progress: SndCIStatusProgress,
+    },
+    SndRcvd {
+        msg_rcpt_status: MsgReceiptStatus,
+        snd_progress: SndCIStatusProgress,
+    },
+    SndErrorAuth,
+    // the field is named `agentError` by the server, but it holds a `SndError`
+    SndError {
+        agent_error: SndError,
+    },
+    // delivery is being retried
+    SndWarning {
+        agent_error: SndError,
+    },
+    // received items
+    RcvNew,
+    RcvRead,
+    Invalid {
+        text: String,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl CIStatus {
+    // Whether the item was sent to (or received by) all of its recipients
+    pub fn is_delivered(&self) -> bool {
+        matches!(
+            self,
+            Self::SndSent {
+                snd_progress: SndCIStatusProgress::Complete
+            } | Self::SndRcvd {
+                snd_progress: SndCIStatusProgress::Complete,
+                ..
+            }
+        )
+    }
+
+    // Whether the item failed to be sent and won't be retried
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::SndErrorAuth | Self::SndError { .. })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SndCIStatusProgress {
+    // sent to some of the members of a group
+    Partial,
+    Complete,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MsgReceiptStatus {
+    Ok,
+    BadMsgHash,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CITimed {
+    pub ttl: u64, // seconds
+    // None until the timer starts (i.e. when the item is read)
+    #[serde(default, with = "timestamp::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum CIForwardedFrom {
+    Contact {
+        chat_name: String,
+        msg_dir: MsgDirection,
+        // None if the original contact or item is no longer available
+        #[serde(skip_serializing_if = "Option::is_none")]
+        contact_id: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_id: Option<u64>,
+    },
+    Group {
+        chat_name: String,
+        msg_dir: MsgDirection,
+        // None if the original group or item is no longer available
+        #[serde(skip_serializing_if = "Option::is_none")]
+        group_id: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        chat_item_id: Option<u64>,
+    },
+    // also used when the server doesn't know where the item was forwarded from
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MsgDirection {
+    Rcv,
+    Snd,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatItemDeletion {
+    pub deleted_chat_item: AChatItem,
+    // item that replaces the deleted one (e.g. "marked deleted" placeholder)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_chat_item: Option<AChatItem>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CIDeleteMode {
+    // delete for everyone
+    Broadcast,
+    // delete only locally
+    Internal,
+    // mark as deleted locally
+    InternalMark,
+}
+
+impl fmt::Display for CIDeleteMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self {
+            Self::Broadcast => "broadcast",
+            Self::Internal => "internal",
+            Self::InternalMark => "internalMark",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Chat {
+    pub chat_info: ChatInfo,
+    // ordered from oldest to newest. Chat lists only include the last item
+    #[serde(default)]
+    pub chat_items: Vec<ChatItem>,
+    pub chat_stats: ChatStats,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatStats {
+    pub unread_count: u64,
+    #[serde(default)]
+    pub unread_mentions: u64,
+    #[serde(default)]
+    pub reports_count: u64,
+    // 0 if there are no unread items
+    pub min_unread_item_id: u64,
+    // whether the chat was manually marked as unread
+    pub unread_chat: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pagination {
+    // the last `count` items of the chat
+    Last { count: u64 },
+    // `count` items after (not including) `item_id`
+    After { item_id: u64, count: u64 },
+    // `count` items before (not including) `item_id`
+    Before { item_id: u64, count: u64 },
+    // `count` items before and after `item_id`, including it
+    Around { item_id: u64, count: u64 },
+    // the unread items, or the last items if there are none
+    Initial { count: u64 },
+}
+
+impl fmt::Display for Pagination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Last { count } => write!(f, "count={}", count),
+            Self::After { item_id, count } => write!(f, "after={} count={}", item_id, count),
+            Self::Before { item_id, count } => write!(f, "before={} count={}", item_id, count),
+            Self::Around { item_id, count } => write!(f, "around={} count={}", item_id, count),
+            Self::Initial { count } => write!(f, "initial={}", count),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactLink {
+    pub address_settings: AddressSettings,
+    pub conn_link_contact: ConnLinkContact,
+    pub short_link_data_set: bool,
+    pub user_contact_link_id: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSettings {
+    pub auto_accept: AutoAccept,
+    pub business_address: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnLinkContact {
+    pub conn_full_link: String,
+    pub conn_short_link: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAccept {
+    pub accept_incognito: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+#[allow(clippy::large_enum_variant)]
+pub enum MsgContent {
+    Text {
+        text: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    // the server requires a preview with links, so link content
+    // without one is kept as `Unknown`
+    Link {
+        text: String,
+        preview: LinkPreview,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Image {
+        text: String,
+        image: String, // Base64 string
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Video {
+        text: String,
+        image: String, // Base64 string
+        duration: u64, // seconds
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Voice {
+        text: String,
+        duration: u64, // seconds
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    File {
+        text: String,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Report {
+        text: String,
+        reason: ReportReason,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Chat {
+        text: String,
+        chat_link: MsgChatLink,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    // content types not supported by this library are kept exactly as they were
+    // received, so that they can be sent back (e.g. when forwarding or quoting)
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl MsgContent {
+    pub fn text(text: &str) -> Self {
+        Self::Text {
+            text: text.to_owned(),
+            _unknown_fields: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkPreview {
+    pub uri: String,
+    pub title: String,
+    pub description: String,
+    pub image: String, // Base64 string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<JsonValue>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportReason {
+    Spam,
+    Content,
+    Community,
+    Profile,
+    Other,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum MsgChatLink {
+    Contact {
+        conn_link: String,
+        profile: Profile,
+        business: bool,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Invitation {
+        inv_link: String,
+        profile: Profile,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Group {
+        conn_link: String,
+        group_profile: GroupProfile,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection {
+    pub conn_id: u64,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionStats {
+    pub conn_agent_version: u64,
+    pub rcv_queues_info: Vec<RcvQueueInfo>,
+    pub snd_queues_info: Vec<SndQueueInfo>,
+    pub ratchet_sync_state: String,
+    pub ratchet_sync_supported: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RcvQueueInfo {
+    pub rcv_server: String, // SMP server URI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rcv_switch_status: Option<String>,
+    pub can_abort_switch: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SndQueueInfo {
+    pub snd_server: String, // SMP server URI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snd_switch_status: Option<String>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+// Reference to a contact in the subscription events,
+// which don't include the whole contact
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactRef {
+    pub contact_id: u64,
+    pub conn_id: u64,
+    pub agent_conn_id: String,
+    pub local_display_name: String,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSubStatus {
+    pub contact: Contact,
+    // None if the contact connection was subscribed successfully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_error: Option<ChatError>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RcvCallInvitation {
+    pub user: User,
+    pub contact: Contact,
+    pub call_type: CallType,
+    // None if the call is not end-to-end encrypted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_key: Option<String>,
+    #[serde(rename = "callUUID")]
+    pub call_uuid: String,
+    #[serde(with = "timestamp")]
+    pub call_ts: DateTime<Utc>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallType {
+    pub media: CallMedia,
+    pub capabilities: CallCapabilities,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CallMedia {
+    Audio,
+    Video,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallCapabilities {
+    pub encryption: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CIFile {
+    pub file_id: u64,
+    pub file_name: String,
+    pub file_size: u64,
+    // location of the file, None until it is received
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_source: Option<CryptoFile>,
+    pub file_status: CIFileStatus,
+    pub file_protocol: FileProtocol,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoFile {
+    pub file_path: String,
+    // present if the file is stored encrypted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_args: Option<JsonValue>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum CIFileStatus {
+    SndStored,
+    SndTransfer {
+        snd_progress: u64,
+        snd_total: u64,
+    },
+    SndComplete,
+    SndCancelled,
+    SndError {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    RcvInvitation,
+    RcvAccepted,
+    RcvTransfer {
+        rcv_progress: u64,
+        rcv_total: u64,
+    },
+    RcvAborted,
+    RcvComplete,
+    RcvCancelled,
+    RcvError {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileProtocol {
+    Smp,
+    Xftp,
+    Local,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTransferMeta {
+    pub file_id: u64,
+    pub file_name: String,
+    pub file_path: String,
+    pub file_size: u64,
+    pub chunk_size: u64,
+    pub cancelled: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+// File transferred over SMP (or inline), as reported by `/fstatus`.
+// XFTP transfers are reported with the status of their chat item instead
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum FileTransfer {
+    Snd {
+        file_transfer_meta: FileTransferMeta,
+        // one transfer per recipient
+        snd_file_transfers: Vec<SndFileTransfer>,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Rcv {
+        rcv_file_transfer: RcvFileTransfer,
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl FileTransfer {
+    // Status of the transfer as it would be shown on its chat item. `chunks` is the
+    // number of chunks transferred (for each recipient of a sent file), as reported
+    // together with the transfer, so the progress is counted in chunks
+    pub fn ci_file_status(&self, chunks: &[u64]) -> CIFileStatus {
+        let progress = chunks.iter().sum();
+        match self {
+            Self::Snd {
+                file_transfer_meta: meta,
+                snd_file_transfers,
+                ..
+            } => {
+                let all_complete = snd_file_transfers
+                    .iter()
+                    .all(|transfer| transfer.file_status == FileStatus::Complete);
+                let any_started = snd_file_transfers
+                    .iter()
+                    .any(|transfer| transfer.file_status != FileStatus::New);
+                if meta.cancelled {
+                    CIFileStatus::SndCancelled
+                } else if !snd_file_transfers.is_empty() && all_complete {
+                    CIFileStatus::SndComplete
+                } else if any_started {
+                    CIFileStatus::SndTransfer {
+                        snd_progress: progress,
+                        snd_total: total_chunks(meta.file_size, meta.chunk_size)
+                            * snd_file_transfers.len() as u64,
+                    }
+                } else {
+                    CIFileStatus::SndStored
+                }
+            }
+            Self::Rcv {
+                rcv_file_transfer: transfer,
+                ..
+            } => match &transfer.file_status {
+                RcvFileStatus::New => CIFileStatus::RcvInvitation,
+                RcvFileStatus::Accepted { .. } => CIFileStatus::RcvAccepted,
+                RcvFileStatus::Connected { .. } => CIFileStatus::RcvTransfer {
+                    rcv_progress: progress,
+                    rcv_total: total_chunks(
+                        transfer.file_invitation.file_size,
+                        transfer.chunk_size,
+                    ),
+                },
+                RcvFileStatus::Complete { .. } => CIFileStatus::RcvComplete,
+                RcvFileStatus::Cancelled { .. } => CIFileStatus::RcvCancelled,
+                RcvFileStatus::Unknown(status) => CIFileStatus::Unknown(status.clone()),
+            },
+            Self::Unknown(transfer) => CIFileStatus::Unknown(transfer.clone()),
+        }
+    }
+}
+
+fn total_chunks(file_size: u64, chunk_size: u64) -> u64 {
+    if chunk_size == 0 {
+        return 0;
+    }
+    file_size.div_ceil(chunk_size)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SndFileTransfer {
+    pub file_id: u64,
+    pub file_name: String,
+    pub file_size: u64,
+    pub chunk_size: u64,
+    pub recipient_display_name: String,
+    pub file_status: FileStatus,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileStatus {
+    New,
+    Accepted,
+    Connected,
+    Complete,
+    Cancelled,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RcvFileTransfer {
+    pub file_id: u64,
+    pub file_invitation: FileInvitation,
+    pub file_status: RcvFileStatus,
+    pub chunk_size: u64,
+    pub cancelled: bool,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub _unknown_fields: HashMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInvitation {
+    pub file_name: String,
+    pub file_size: u64,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub _unknown_fields: HashMap<String, JsonValue>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum RcvFileStatus {
+    New,
+    Accepted {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Connected {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Complete {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    Cancelled {
+        #[serde(flatten)]
+        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        _unknown_fields: HashMap<String, JsonValue>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserContactRequest {
     pub contact_request_id: u64,
     pub local_display_name: String,
     pub profile: Profile,
+    #[serde(with = "timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "timestamp")]
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -364,9 +1686,66 @@ pub struct ComposedMessage {
     pub mentions: Mentions,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct Mentions {}
+pub struct UpdatedMessage {
+    pub msg_content: MsgContent,
+    pub mentions: Mentions,
+}
+
+// Members mentioned in a message, by the name used in the text
+// (e.g. `@alice`) to their group member ID
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Mentions(pub HashMap<String, u64>);
+
+impl ComposedMessage {
+    pub fn builder() -> ComposedMessageBuilder {
+        ComposedMessageBuilder::default()
+    }
+}
+
+// Builds a text message, keeping the mentions in sync with the text
+#[derive(Default, Debug, Clone)]
+pub struct ComposedMessageBuilder {
+    text: String,
+    mentions: Mentions,
+    quoted_item_id: Option<u64>,
+}
+
+impl ComposedMessageBuilder {
+    pub fn text(mut self, text: &str) -> Self {
+        self.text.push_str(text);
+        self
+    }
+
+    // Inserts `@name` into the text and registers the mention of the member
+    pub fn mention(mut self, name: &str, group_member_id: u64) -> Self {
+        let mention = FormattedText::new(
+            "",
+            Format::Mention {
+                member_name: name.to_owned(),
+            },
+        );
+        self.text.push_str(&mention.to_string());
+        self.mentions.0.insert(name.to_owned(), group_member_id);
+        self
+    }
+
+    pub fn quote(mut self, item_id: u64) -> Self {
+        self.quoted_item_id = Some(item_id);
+        self
+    }
+
+    pub fn build(self) -> ComposedMessage {
+        ComposedMessage {
+            file_path: None,
+            quoted_item_id: self.quoted_item_id,
+            msg_content: MsgContent::text(&self.text),
+            mentions: self.mentions,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum ChatType {
@@ -375,13 +1754,71 @@ pub enum ChatType {
     ContactRequest,
 }
 
-impl ToString for ChatType {
-    fn to_string(&self) -> String {
-        match self {
+impl fmt::Display for ChatType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self {
             Self::Direct => "@",
             Self::Group => "#",
             Self::ContactRequest => "<@",
+        };
+        write!(f, "{}", prefix)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatRef {
+    pub chat_type: ChatType,
+    pub chat_id: u64,
+}
+
+impl ChatRef {
+    pub fn new(chat_type: ChatType, chat_id: u64) -> Self {
+        Self { chat_type, chat_id }
+    }
+
+    pub fn direct(contact_id: u64) -> Self {
+        Self::new(ChatType::Direct, contact_id)
+    }
+
+    pub fn group(group_id: u64) -> Self {
+        Self::new(ChatType::Group, group_id)
+    }
+}
+
+impl fmt::Display for ChatRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.chat_type, self.chat_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatDeleteMode {
+    // delete both the entity and its messages
+    Full { notify: bool },
+    // delete the entity, keeping its messages locally
+    Entity { notify: bool },
+    // only delete the messages
+    Messages,
+}
+
+// How long the items of a chat are kept before being deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatTtl {
+    // use the default TTL of the user
+    Default,
+    // never delete the items of this chat, whatever the default TTL is
+    Never,
+    After(std::time::Duration),
+}
+
+impl fmt::Display for ChatDeleteMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let onoff = |on: bool| if on { "on" } else { "off" };
+        match self {
+            Self::Full { notify } => write!(f, "full notify={}", onoff(*notify)),
+            Self::Entity { notify } => write!(f, "entity notify={}", onoff(*notify)),
+            Self::Messages => write!(f, "messages"),
         }
-        .to_owned()
     }
 }
@@ -0,0 +1,31 @@
+mod common;
+
+use common::{payload, response};
+use simplex_chat::*;
+
+#[test]
+fn chat_settings() {
+    let ChatResponse::ContactConnected { contact, .. } = response("contact_connected") else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(contact.chat_settings.enable_ntfs, MsgFilter::All);
+}
+
+#[test]
+fn unknown_msg_filter() {
+    // the contact must still be received with a filter added by a newer server
+    let json =
+        payload("contact_connected").replace(r#""enableNtfs": "all""#, r#""enableNtfs": "urgent""#);
+    let resp: ChatResponse = serde_json::from_str(&json).unwrap();
+    let ChatResponse::ContactConnected { contact, .. } = resp else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(
+        contact.chat_settings.enable_ntfs,
+        MsgFilter::Unknown("urgent".into())
+    );
+    assert_eq!(
+        serde_json::to_value(&contact.chat_settings.enable_ntfs).unwrap(),
+        "urgent"
+    );
+}
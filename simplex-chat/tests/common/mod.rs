@@ -0,0 +1,23 @@
+#![allow(dead_code)]
+
+use simplex_chat::ChatResponse;
+use std::fs;
+use std::path::Path;
+
+// Sample responses of the chat server, stored in `tests/payloads`
+pub fn payload(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/payloads")
+        .join(format!("{}.json", name));
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", path.display(), err))
+}
+
+// Parses a sample response, which must match one of the typed variants
+pub fn response(name: &str) -> ChatResponse {
+    let resp = serde_json::from_str(&payload(name)).unwrap();
+    if let ChatResponse::Unknown(_) = resp {
+        panic!("The response in {} was not recognized", name);
+    }
+    resp
+}
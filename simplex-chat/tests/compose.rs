@@ -0,0 +1,65 @@
+use simplex_chat::*;
+
+fn text(msg: &ComposedMessage) -> &str {
+    let MsgContent::Text { text, .. } = &msg.msg_content else {
+        panic!("Unexpected content type");
+    };
+    text
+}
+
+#[test]
+fn mention_plain_name() {
+    let msg = ComposedMessage::builder()
+        .text("hi ")
+        .mention("alice", 3)
+        .text("!")
+        .build();
+    assert_eq!(text(&msg), "hi @alice!");
+    assert_eq!(msg.mentions.0.len(), 1);
+    assert_eq!(msg.mentions.0["alice"], 3);
+}
+
+#[test]
+fn mention_spaced_name() {
+    let msg = ComposedMessage::builder()
+        .mention("alice smith", 3)
+        .text(" hi")
+        .build();
+    assert_eq!(text(&msg), "@'alice smith' hi");
+    assert_eq!(msg.mentions.0["alice smith"], 3);
+}
+
+#[test]
+fn mention_quoted_names() {
+    // quotes inside a name are read as part of an unquoted name
+    let msg = ComposedMessage::builder().mention("o'neil", 4).build();
+    assert_eq!(text(&msg), "@o'neil");
+    assert_eq!(msg.mentions.0["o'neil"], 4);
+
+    // punctuation at the end would be dropped from an unquoted name
+    let msg = ComposedMessage::builder().mention("bob.", 5).build();
+    assert_eq!(text(&msg), "@'bob.'");
+    assert_eq!(msg.mentions.0["bob."], 5);
+}
+
+#[test]
+fn mentions_map() {
+    let msg = ComposedMessage::builder()
+        .mention("alice", 3)
+        .text(", ")
+        .mention("bob", 4)
+        .text(", ")
+        .mention("alice", 3)
+        .quote(10)
+        .build();
+    assert_eq!(text(&msg), "@alice, @bob, @alice");
+    assert_eq!(msg.quoted_item_id, Some(10));
+    assert_eq!(
+        msg.mentions,
+        Mentions([("alice".to_owned(), 3), ("bob".to_owned(), 4)].into())
+    );
+    assert_eq!(
+        serde_json::to_value(&msg.mentions).unwrap(),
+        serde_json::json!({"alice": 3, "bob": 4})
+    );
+}
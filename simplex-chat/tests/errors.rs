@@ -0,0 +1,94 @@
+mod common;
+
+use common::response;
+use simplex_chat::*;
+
+fn chat_error(name: &str) -> ChatError {
+    let ChatResponse::ChatCmdError { chat_error, .. } = response(name) else {
+        panic!("Unexpected response type");
+    };
+    chat_error
+}
+
+#[test]
+fn store_errors() {
+    let error = chat_error("error_user_contact_link_not_found");
+    assert!(matches!(
+        error,
+        ChatError::ErrorStore {
+            store_error: StoreError::UserContactLinkNotFound,
+            ..
+        }
+    ));
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "store error: contact address not found");
+
+    let error = chat_error("error_group_link_not_found");
+    assert!(matches!(
+        error,
+        ChatError::ErrorStore {
+            store_error: StoreError::GroupLinkNotFound { .. },
+            ..
+        }
+    ));
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "store error: group link not found");
+
+    // not found, but not a missing link
+    let error = chat_error("error_group_not_found");
+    assert!(matches!(
+        error,
+        ChatError::ErrorStore {
+            store_error: StoreError::GroupNotFound { group_id: 9 },
+            ..
+        }
+    ));
+    assert!(error.is_not_found());
+    assert_eq!(error.to_string(), "store error: group 9 not found");
+}
+
+#[test]
+fn chat_errors() {
+    let error = chat_error("error_contact_not_found");
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "chat error: contact bob not found");
+
+    let error = chat_error("error_group_user_role");
+    assert_eq!(error.kind(), ErrorKind::Permission);
+    assert!(error.is_permission());
+    assert_eq!(
+        error.to_string(),
+        "chat error: this action requires the admin role"
+    );
+}
+
+#[test]
+fn agent_errors() {
+    let error = chat_error("error_agent_broker_timeout");
+    assert_eq!(error.kind(), ErrorKind::Retryable);
+    assert!(error.is_retryable());
+    assert_eq!(
+        error.to_string(),
+        "agent error: server smp://abcd@smp.example.com error: timeout"
+    );
+
+    let error = chat_error("error_agent_conn_not_found");
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(
+        error.to_string(),
+        "agent error: connection error: connection not found"
+    );
+}
+
+#[test]
+fn other_errors() {
+    let error = chat_error("error_database");
+    assert_eq!(error.kind(), ErrorKind::Other);
+    assert!(!error.is_retryable() && !error.is_not_found() && !error.is_permission());
+
+    let error: ChatError =
+        serde_json::from_str(r#"{"type":"errorRemoteCtrl","remoteCtrlError":{"type":"inactive"}}"#)
+            .unwrap();
+    assert!(matches!(error, ChatError::Unknown(_)));
+    assert_eq!(error.kind(), ErrorKind::Other);
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use common::response;
+use simplex_chat::*;
+
+#[test]
+fn contact_events() {
+    for name in [
+        "contact_connecting",
+        "contact_snd_ready",
+        "contact_deleted_by_contact",
+        "call_ended",
+    ] {
+        let contact = match response(name) {
+            ChatResponse::ContactConnecting { contact, .. }
+            | ChatResponse::ContactSndReady { contact, .. }
+            | ChatResponse::ContactDeletedByContact { contact, .. }
+            | ChatResponse::CallEnded { contact, .. } => contact,
+            resp => panic!("Unexpected response type in {}: {:?}", name, resp),
+        };
+        assert_eq!(contact.contact_id, 2, "{}", name);
+        assert_eq!(contact.profile.display_name, "alice", "{}", name);
+    }
+
+    let ChatResponse::ContactUpdated {
+        from_contact,
+        to_contact,
+        ..
+    } = response("contact_updated")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(from_contact.local_display_name, "alice");
+    assert_eq!(to_contact.local_display_name, "alice_1");
+    assert_eq!(from_contact.profile.display_name, "alice");
+    assert_eq!(from_contact.profile.full_name, "");
+    assert_eq!(to_contact.profile.display_name, "alice_1");
+    assert_eq!(to_contact.profile.full_name, "Alice");
+    assert!(!to_contact._unknown_fields.contains_key("profile"));
+}
+
+#[test]
+fn call_invitation() {
+    let ChatResponse::CallInvitation {
+        call_invitation, ..
+    } = response("call_invitation")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(call_invitation.contact.contact_id, 2);
+    assert_eq!(call_invitation.contact.profile.display_name, "alice");
+    assert_eq!(call_invitation.call_type.media, CallMedia::Video);
+    assert!(call_invitation.call_type.capabilities.encryption);
+    assert!(call_invitation.shared_key.is_some());
+    assert_eq!(
+        call_invitation.call_uuid,
+        "6e3b8f0a-8f3e-4c55-9f1a-2d8a1f5c7b42"
+    );
+}
+
+#[test]
+fn group_events() {
+    let ChatResponse::LeftMember { member, .. } = response("left_member") else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(member.group_member_id, 3);
+    assert_eq!(member.member_status, GroupMemberStatus::Left);
+    assert!(!member.is_active());
+
+    let ChatResponse::GroupDeleted {
+        group_info, member, ..
+    } = response("group_deleted")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(group_info.group_id, 1);
+    assert_eq!(member.member_role, GroupMemberRole::Owner);
+}
+
+#[test]
+fn subscription_events() {
+    let ChatResponse::ContactSubSummary {
+        contact_subscriptions,
+        ..
+    } = response("contact_sub_summary")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(contact_subscriptions.len(), 2);
+    assert!(contact_subscriptions[0].contact_error.is_none());
+    assert!(matches!(
+        contact_subscriptions[1].contact_error,
+        Some(ChatError::ErrorAgent { .. })
+    ));
+
+    let ChatResponse::ContactSubError { chat_error, .. } = response("contact_sub_error") else {
+        panic!("Unexpected response type");
+    };
+    assert!(chat_error.is_not_found());
+
+    for name in ["contacts_subscribed", "contacts_disconnected"] {
+        let (server, contact_refs) = match response(name) {
+            ChatResponse::ContactsSubscribed {
+                server,
+                contact_refs,
+                ..
+            }
+            | ChatResponse::ContactsDisconnected {
+                server,
+                contact_refs,
+                ..
+            } => (server, contact_refs),
+            resp => panic!("Unexpected response type in {}: {:?}", name, resp),
+        };
+        assert!(server.starts_with("smp://"), "{}", name);
+        assert_eq!(contact_refs.len(), 1, "{}", name);
+        assert_eq!(contact_refs[0].contact_id, 2, "{}", name);
+        assert_eq!(contact_refs[0].local_display_name, "alice", "{}", name);
+    }
+}
+
+#[test]
+fn host_events() {
+    let ChatResponse::HostConnected {
+        protocol,
+        transport_host,
+        ..
+    } = response("host_connected")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(protocol, "smp");
+    assert_eq!(transport_host, "smp4.simplex.im");
+
+    let ChatResponse::HostDisconnected {
+        protocol,
+        transport_host,
+        ..
+    } = response("host_disconnected")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(protocol, "xftp");
+    assert_eq!(transport_host, "xftp1.simplex.im");
+}
+
+#[test]
+fn error_events() {
+    let ChatResponse::ChatErrors { chat_errors, .. } = response("chat_errors") else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(chat_errors.len(), 1);
+    assert!(chat_errors[0].is_not_found());
+
+    let ChatResponse::MessageError {
+        severity,
+        error_message,
+        ..
+    } = response("message_error")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(severity, "error");
+    assert_eq!(error_message, "x.msg.new: duplicate message");
+}
+
+#[test]
+fn contact_profile() {
+    let ChatResponse::ContactConnected { contact, .. } = response("contact_connected") else {
+        panic!("Unexpected response type");
+    };
+    let profile = &contact.profile;
+    assert_eq!(profile.profile_id, 2);
+    assert_eq!(profile.display_name, "alice");
+    assert_eq!(profile.local_alias, "");
+    let preferences = profile.preferences.as_ref().unwrap();
+    let timed_messages = preferences.timed_messages.as_ref().unwrap();
+    assert_eq!(timed_messages.allow, FeatureAllowed::Yes);
+    assert_eq!(timed_messages.ttl, Some(86400));
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{payload, response};
+use simplex_chat::*;
+
+#[test]
+fn rcv_file_status() {
+    let ChatResponse::FileTransferStatus {
+        file_transfer_status: (file_transfer, chunks),
+        ..
+    } = response("file_transfer_status_rcv")
+    else {
+        panic!("Unexpected response type");
+    };
+
+    // 1 MiB in chunks of 15780 bytes
+    assert!(matches!(
+        file_transfer.ci_file_status(&chunks),
+        CIFileStatus::RcvTransfer {
+            rcv_progress: 12,
+            rcv_total: 67
+        }
+    ));
+}
+
+#[test]
+fn snd_file_status() {
+    let ChatResponse::FileTransferStatus {
+        file_transfer_status: (file_transfer, chunks),
+        ..
+    } = response("file_transfer_status_snd")
+    else {
+        panic!("Unexpected response type");
+    };
+
+    // sent to 2 recipients, 7 chunks each
+    assert!(matches!(
+        file_transfer.ci_file_status(&chunks),
+        CIFileStatus::SndTransfer {
+            snd_progress: 10,
+            snd_total: 14
+        }
+    ));
+}
+
+#[test]
+fn rcv_file_events() {
+    let ChatResponse::RcvFileError {
+        chat_item_,
+        agent_error,
+        rcv_file_transfer,
+        ..
+    } = response("rcv_file_error")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert!(chat_item_.is_none());
+    assert!(matches!(agent_error, AgentError::Smp { .. }));
+    assert_eq!(rcv_file_transfer.file_id, 7);
+
+    let ChatResponse::RcvFileCancelled {
+        rcv_file_transfer, ..
+    } = response("rcv_file_cancelled")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert!(rcv_file_transfer.cancelled);
+    assert!(matches!(
+        rcv_file_transfer.file_status,
+        RcvFileStatus::Cancelled { .. }
+    ));
+
+    let ChatResponse::RcvFileComplete { chat_item, .. } = response("rcv_file_complete") else {
+        panic!("Unexpected response type");
+    };
+    let file = chat_item.chat_item.file.unwrap();
+    assert_eq!(file.file_id, 7);
+    assert_eq!(
+        file.file_source.unwrap().file_path,
+        "/home/bot/files/report.pdf"
+    );
+    assert_eq!(file.file_protocol, FileProtocol::Smp);
+}
+
+#[test]
+fn unknown_file_protocol() {
+    // the chat item must still be received with a protocol added by a newer server
+    let json = payload("rcv_file_complete")
+        .replace(r#""fileProtocol": "smp""#, r#""fileProtocol": "ipfs""#);
+    let resp: ChatResponse = serde_json::from_str(&json).unwrap();
+    let ChatResponse::RcvFileComplete { chat_item, .. } = resp else {
+        panic!("Unexpected response type");
+    };
+    let file = chat_item.chat_item.file.unwrap();
+    assert_eq!(file.file_protocol, FileProtocol::Unknown("ipfs".into()));
+}
@@ -0,0 +1,163 @@
+use serde_json::Value as JsonValue;
+use simplex_chat::*;
+
+// Parses message content and checks that it is serialized back unchanged
+fn round_trip(json: &str) -> MsgContent {
+    let content: MsgContent = serde_json::from_str(json).unwrap();
+    let expected: JsonValue = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&content).unwrap(), expected);
+    content
+}
+
+#[test]
+fn text() {
+    let content = round_trip(r#"{"type":"text","text":"hello"}"#);
+    assert!(matches!(content, MsgContent::Text { text, .. } if text == "hello"));
+}
+
+#[test]
+fn link() {
+    let content = round_trip(
+        r#"{"type":"link","text":"https://simplex.chat","preview":{"uri":"https://simplex.chat","title":"SimpleX Chat","description":"Private messaging","image":"data:image/jpg;base64,/9j/4AAQ"}}"#,
+    );
+    let MsgContent::Link { preview, .. } = content else {
+        panic!("Unexpected content type");
+    };
+    assert_eq!(preview.uri, "https://simplex.chat");
+    assert_eq!(preview.title, "SimpleX Chat");
+    assert!(preview.content.is_none());
+
+    let content = round_trip(
+        r#"{"type":"link","text":"https://simplex.chat","preview":{"uri":"https://simplex.chat","title":"","description":"","image":"","content":{"type":"page"}}}"#,
+    );
+    let MsgContent::Link { preview, .. } = content else {
+        panic!("Unexpected content type");
+    };
+    assert!(preview.content.is_some());
+}
+
+#[test]
+fn link_without_preview() {
+    let json = r#"{"type":"link","text":"hi"}"#;
+    let content: MsgContent = serde_json::from_str(json).unwrap();
+    assert!(matches!(content, MsgContent::Unknown(_)));
+    assert_eq!(serde_json::to_string(&content).unwrap(), json);
+}
+
+#[test]
+fn image() {
+    let content =
+        round_trip(r#"{"type":"image","text":"","image":"data:image/jpg;base64,/9j/4AAQ"}"#);
+    assert!(matches!(content, MsgContent::Image { .. }));
+}
+
+#[test]
+fn video() {
+    let content = round_trip(
+        r#"{"type":"video","text":"look","image":"data:image/jpg;base64,/9j/4AAQ","duration":42}"#,
+    );
+    assert!(matches!(content, MsgContent::Video { duration: 42, .. }));
+}
+
+#[test]
+fn voice() {
+    let content = round_trip(r#"{"type":"voice","text":"","duration":7}"#);
+    assert!(matches!(content, MsgContent::Voice { duration: 7, .. }));
+}
+
+#[test]
+fn file() {
+    let content = round_trip(r#"{"type":"file","text":"report.pdf"}"#);
+    assert!(matches!(content, MsgContent::File { .. }));
+}
+
+#[test]
+fn report() {
+    let content = round_trip(r#"{"type":"report","text":"","reason":"spam"}"#);
+    assert!(matches!(
+        content,
+        MsgContent::Report {
+            reason: ReportReason::Spam,
+            ..
+        }
+    ));
+
+    let content = round_trip(r#"{"type":"report","text":"","reason":"illegal"}"#);
+    assert!(matches!(
+        content,
+        MsgContent::Report {
+            reason: ReportReason::Unknown(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn chat() {
+    let content = round_trip(
+        r#"{"type":"chat","text":"","chatLink":{"type":"contact","connLink":"https://simplex.chat/a#abc","profile":{"displayName":"alice","fullName":"Alice"},"business":false}}"#,
+    );
+    assert!(matches!(
+        content,
+        MsgContent::Chat {
+            chat_link: MsgChatLink::Contact {
+                business: false,
+                ..
+            },
+            ..
+        }
+    ));
+
+    let content = round_trip(
+        r#"{"type":"chat","text":"","chatLink":{"type":"invitation","invLink":"https://simplex.chat/i#abc","profile":{"displayName":"bob","fullName":""}}}"#,
+    );
+    assert!(matches!(
+        content,
+        MsgContent::Chat {
+            chat_link: MsgChatLink::Invitation { .. },
+            ..
+        }
+    ));
+
+    let content = round_trip(
+        r#"{"type":"chat","text":"","chatLink":{"type":"group","connLink":"https://simplex.chat/g#abc","groupProfile":{"displayName":"team","fullName":"","description":"Team chat"}}}"#,
+    );
+    assert!(matches!(
+        content,
+        MsgContent::Chat {
+            chat_link: MsgChatLink::Group { .. },
+            ..
+        }
+    ));
+
+    let content =
+        round_trip(r#"{"type":"chat","text":"","chatLink":{"type":"channel","connLink":"x"}}"#);
+    assert!(matches!(
+        content,
+        MsgContent::Chat {
+            chat_link: MsgChatLink::Unknown(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn unknown_fields() {
+    let content = round_trip(r#"{"type":"text","text":"hello","format":"new"}"#);
+    let MsgContent::Text {
+        _unknown_fields, ..
+    } = content
+    else {
+        panic!("Unexpected content type");
+    };
+    assert_eq!(_unknown_fields["format"], "new");
+}
+
+#[test]
+fn unknown() {
+    // kept byte for byte, including the order of the fields
+    let json = r#"{"type":"poll","text":"lunch?","options":["pizza","sushi"],"multiple":false,"closesAt":null}"#;
+    let content: MsgContent = serde_json::from_str(json).unwrap();
+    assert!(matches!(content, MsgContent::Unknown(_)));
+    assert_eq!(serde_json::to_string(&content).unwrap(), json);
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{payload, response};
+use simplex_chat::*;
+
+fn calls_allowed(user: &User) -> FeatureAllowed {
+    let preferences = user.profile.preferences.as_ref().unwrap();
+    preferences.calls.as_ref().unwrap().allow.clone()
+}
+
+#[test]
+fn user_preferences() {
+    let ChatResponse::ContactConnected { user, .. } = response("contact_connected") else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(calls_allowed(&user), FeatureAllowed::No);
+}
+
+#[test]
+fn unknown_feature_allowed() {
+    // the user must still be received with a value added by a newer server
+    let json = payload("contact_connected").replacen(
+        r#""calls": {
+          "allow": "no""#,
+        r#""calls": {
+          "allow": "ask""#,
+        1,
+    );
+    let resp: ChatResponse = serde_json::from_str(&json).unwrap();
+    let ChatResponse::ContactConnected { user, .. } = resp else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(calls_allowed(&user), FeatureAllowed::Unknown("ask".into()));
+}
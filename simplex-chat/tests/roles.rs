@@ -0,0 +1,106 @@
+mod common;
+
+use common::response;
+use simplex_chat::*;
+
+fn role(role: &str) -> GroupMemberRole {
+    serde_json::from_str(&format!("\"{}\"", role)).unwrap()
+}
+
+fn member() -> GroupMember {
+    let ChatResponse::JoinedGroupMember { member, .. } = response("joined_group_member") else {
+        panic!("Unexpected response type");
+    };
+    member
+}
+
+#[test]
+fn role_ordering() {
+    let roles = [
+        GroupMemberRole::Observer,
+        GroupMemberRole::Author,
+        GroupMemberRole::Member,
+        GroupMemberRole::Moderator,
+        GroupMemberRole::Admin,
+        GroupMemberRole::Owner,
+    ];
+    for (i, lower) in roles.iter().enumerate() {
+        for higher in &roles[i + 1..] {
+            assert!(lower < higher, "{} < {}", lower, higher);
+        }
+        assert!(lower <= lower);
+    }
+
+    // unknown roles can only be compared with themselves
+    let unknown = role("superadmin");
+    assert_eq!(
+        unknown.partial_cmp(&unknown),
+        Some(std::cmp::Ordering::Equal)
+    );
+    for known in &roles {
+        assert_eq!(unknown.partial_cmp(known), None);
+        assert_eq!(known.partial_cmp(&unknown), None);
+    }
+}
+
+#[test]
+fn role_names() {
+    for name in [
+        "observer",
+        "author",
+        "member",
+        "moderator",
+        "admin",
+        "owner",
+    ] {
+        let role = role(name);
+        assert!(!matches!(role, GroupMemberRole::Unknown(_)));
+        assert_eq!(role.to_string(), name);
+    }
+}
+
+#[test]
+fn member_permissions() {
+    // (role, can_moderate, is_admin)
+    let cases = [
+        ("observer", false, false),
+        ("author", false, false),
+        ("member", false, false),
+        ("moderator", true, false),
+        ("admin", true, true),
+        ("owner", true, true),
+        ("superadmin", false, false),
+    ];
+    let mut member = member();
+    for (name, can_moderate, is_admin) in cases {
+        member.member_role = role(name);
+        assert_eq!(member.can_moderate(), can_moderate, "{}", name);
+        assert_eq!(member.is_admin(), is_admin, "{}", name);
+    }
+}
+
+#[test]
+fn member_activity() {
+    // (status, is_active)
+    let cases = [
+        ("rejected", false),
+        ("removed", false),
+        ("left", false),
+        ("deleted", false),
+        ("invited", false),
+        ("pending_approval", false),
+        ("pending_review", false),
+        ("introduced", true),
+        ("intro-inv", true),
+        ("accepted", true),
+        ("announced", true),
+        ("connected", true),
+        ("complete", true),
+        ("creator", true),
+    ];
+    let mut member = member();
+    for (status, is_active) in cases {
+        member.member_status = serde_json::from_str(&format!("\"{}\"", status)).unwrap();
+        assert_eq!(member.is_active(), is_active, "{}", status);
+    }
+}
@@ -0,0 +1,150 @@
+use simplex_chat::*;
+
+#[test]
+fn delivery_statuses() {
+    // (status, is_delivered, is_failed)
+    let statuses = [
+        (r#"{"type":"sndNew"}"#, false, false),
+        (
+            r#"{"type":"sndSent","sndProgress":"partial"}"#,
+            false,
+            false,
+        ),
+        (
+            r#"{"type":"sndSent","sndProgress":"complete"}"#,
+            true,
+            false,
+        ),
+        (
+            r#"{"type":"sndRcvd","msgRcptStatus":"ok","sndProgress":"partial"}"#,
+            false,
+            false,
+        ),
+        (
+            r#"{"type":"sndRcvd","msgRcptStatus":"badMsgHash","sndProgress":"complete"}"#,
+            true,
+            false,
+        ),
+        (r#"{"type":"sndErrorAuth"}"#, false, true),
+        (
+            r#"{"type":"sndError","agentError":{"type":"auth"}}"#,
+            false,
+            true,
+        ),
+        (
+            r#"{"type":"sndError","agentError":{"type":"relay","srvError":{"type":"host"}}}"#,
+            false,
+            true,
+        ),
+        (
+            r#"{"type":"sndWarning","agentError":{"type":"quota"}}"#,
+            false,
+            false,
+        ),
+        (
+            r#"{"type":"sndWarning","agentError":{"type":"proxy","proxyServer":"smp4.simplex.im","srvError":{"type":"version"}}}"#,
+            false,
+            false,
+        ),
+        (r#"{"type":"rcvNew"}"#, false, false),
+        (r#"{"type":"rcvRead"}"#, false, false),
+        (r#"{"type":"invalid","text":"bad status"}"#, false, false),
+        (r#"{"type":"sndExpired"}"#, false, false),
+    ];
+    for (json, is_delivered, is_failed) in statuses {
+        let status: CIStatus = serde_json::from_str(json).unwrap();
+        assert_eq!(status.is_delivered(), is_delivered, "{}", json);
+        assert_eq!(status.is_failed(), is_failed, "{}", json);
+    }
+}
+
+#[test]
+fn known_statuses() {
+    let status: CIStatus =
+        serde_json::from_str(r#"{"type":"sndRcvd","msgRcptStatus":"ok","sndProgress":"complete"}"#)
+            .unwrap();
+    assert_eq!(
+        status,
+        CIStatus::SndRcvd {
+            msg_rcpt_status: MsgReceiptStatus::Ok,
+            snd_progress: SndCIStatusProgress::Complete
+        }
+    );
+
+    let status: CIStatus = serde_json::from_str(r#"{"type":"sndExpired"}"#).unwrap();
+    assert!(matches!(status, CIStatus::Unknown(_)));
+}
+
+#[test]
+fn snd_errors() {
+    // (status, error, message)
+    let statuses = [
+        (
+            r#"{"type":"sndError","agentError":{"type":"auth"}}"#,
+            SndError::Auth,
+            "recipient not found",
+        ),
+        (
+            r#"{"type":"sndError","agentError":{"type":"expired"}}"#,
+            SndError::Expired,
+            "message expired",
+        ),
+        (
+            r#"{"type":"sndWarning","agentError":{"type":"quota"}}"#,
+            SndError::Quota,
+            "recipient queue is full",
+        ),
+        (
+            r#"{"type":"sndError","agentError":{"type":"relay","srvError":{"type":"host"}}}"#,
+            SndError::Relay {
+                srv_error: SrvError::Host,
+            },
+            "server error: host unavailable",
+        ),
+        (
+            r#"{"type":"sndWarning","agentError":{"type":"proxy","proxyServer":"smp4.simplex.im","srvError":{"type":"version"}}}"#,
+            SndError::Proxy {
+                proxy_server: "smp4.simplex.im".to_owned(),
+                srv_error: SrvError::Version,
+            },
+            "proxy smp4.simplex.im error: unsupported version",
+        ),
+        (
+            r#"{"type":"sndError","agentError":{"type":"proxyRelay","proxyServer":"smp4.simplex.im","srvError":{"type":"other","srvError":"BLOCKED"}}}"#,
+            SndError::ProxyRelay {
+                proxy_server: "smp4.simplex.im".to_owned(),
+                srv_error: SrvError::Other {
+                    srv_error: "BLOCKED".to_owned(),
+                },
+            },
+            "server error via proxy smp4.simplex.im: BLOCKED",
+        ),
+        (
+            r#"{"type":"sndError","agentError":{"type":"other","sndError":"INTERNAL"}}"#,
+            SndError::Other {
+                snd_error: "INTERNAL".to_owned(),
+            },
+            "INTERNAL",
+        ),
+    ];
+    for (json, expected, message) in statuses {
+        let error = match serde_json::from_str(json).unwrap() {
+            CIStatus::SndError { agent_error } | CIStatus::SndWarning { agent_error } => {
+                agent_error
+            }
+            status => panic!("Unexpected status {:?}", status),
+        };
+        assert_eq!(error, expected, "{}", json);
+        assert_eq!(error.to_string(), message, "{}", json);
+    }
+
+    let status: CIStatus =
+        serde_json::from_str(r#"{"type":"sndError","agentError":{"type":"blocked","info":{}}}"#)
+            .unwrap();
+    assert!(matches!(
+        status,
+        CIStatus::SndError {
+            agent_error: SndError::Unknown(_)
+        }
+    ));
+}
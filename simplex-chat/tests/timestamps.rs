@@ -0,0 +1,66 @@
+mod common;
+
+use chrono::{DateTime, Utc};
+use common::{payload, response};
+use simplex_chat::*;
+
+fn ts(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+#[test]
+fn contact_timestamps() {
+    let ChatResponse::ContactConnected { contact, .. } = response("contact_connected") else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(contact.created_at, ts("2025-06-02T10:15:42.123456Z"));
+    assert_eq!(contact.updated_at, ts("2025-06-02T10:16:03.5Z"));
+}
+
+#[test]
+fn group_timestamps() {
+    let ChatResponse::JoinedGroupMember {
+        group_info, member, ..
+    } = response("joined_group_member")
+    else {
+        panic!("Unexpected response type");
+    };
+    assert_eq!(group_info.created_at, ts("2025-06-02T10:15:42.123456Z"));
+    assert_eq!(group_info.updated_at, ts("2025-06-02T10:16:03.5Z"));
+    assert_eq!(member.created_at, ts("2025-06-02T10:15:42.123456Z"));
+    assert_eq!(member.updated_at, ts("2025-06-02T10:16:03.5Z"));
+}
+
+#[test]
+fn chat_item_timestamps() {
+    let ChatResponse::RcvFileComplete { chat_item, .. } = response("rcv_file_complete") else {
+        panic!("Unexpected response type");
+    };
+    let meta = &chat_item.chat_item.meta;
+    assert_eq!(meta.created_at, ts("2025-06-02T10:15:42.123456Z"));
+    assert_eq!(meta.updated_at, ts("2025-06-02T10:15:42.123456Z"));
+}
+
+#[test]
+fn contact_request_timestamps() {
+    let chat_info: ChatInfo = serde_json::from_str(&payload("chat_info_contact_request")).unwrap();
+    let ChatInfo::ContactRequest {
+        contact_request, ..
+    } = chat_info
+    else {
+        panic!("Unexpected chat info type");
+    };
+    assert_eq!(
+        contact_request.created_at,
+        ts("2025-06-02T10:15:42.123456Z")
+    );
+    assert_eq!(contact_request.updated_at, ts("2025-06-02T10:16:03.5Z"));
+}
+
+#[test]
+fn missing_timestamps() {
+    // both timestamps are always sent by the server, so they are required
+    let json = r#"{"type":"contactRequest","contactRequest":{"contactRequestId":1,"localDisplayName":"carol","profile":{"displayName":"carol","fullName":""}}}"#;
+    let chat_info: ChatInfo = serde_json::from_str(json).unwrap();
+    assert!(matches!(chat_info, ChatInfo::Unknown(_)));
+}